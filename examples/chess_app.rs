@@ -1,18 +1,35 @@
 #![windows_subsystem = "windows"]
 
-use chess::{BoardStatus, ChessMove, Color, MoveGen};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chess::{ChessMove, Color, EMPTY};
 use iced::{
-    Alignment, Element, Task, Theme,
+    Alignment, Element, Subscription, Task, Theme,
     widget::{button, column, horizontal_space, row, text},
 };
 use iced_chess::{
-    chess::{BoardRole, GameState},
-    widget::ChessBoard,
+    chess::{BoardRole, GameOutcome, GameState, GameTree, NodeId},
+    clock::{self, Clock, ClockEvent, TimeControl},
+    engine::{self, Level, SearchResult, Searcher},
+    piece_set::PieceSet,
+    setup::Setup,
+    uci::Score,
+    widget::{
+        CapturedPieces, ChessBoard, ChessClock, EvalBar, GameControls, NotationPanel, SetupPanel,
+        chess_board::{
+            cursor::Focus,
+            shape::{self, Shape},
+            sound::{ChessBoardSound, SoundType},
+        },
+        game_controls::Control,
+    },
 };
 
 fn main() -> iced::Result {
     iced::application(ChessApp::new, ChessApp::update, ChessApp::view)
         .title(ChessApp::title)
+        .subscription(ChessApp::subscription)
         .theme(ChessApp::theme)
         .decorations(true)
         .antialiasing(true)
@@ -22,30 +39,70 @@ fn main() -> iced::Result {
 #[derive(Debug, Clone)]
 enum Message {
     OnMove(ChessMove),
-    Flip,
+    OnPremove(Vec<ChessMove>),
+    EngineMove(NodeId, SearchResult),
+    Control(Control),
     Restart,
-
-    Back,
-    Next,
-    Set(usize),
+    Tick(Instant),
+    Select(NodeId),
+    Focus(Option<Focus>),
+    Edit(Setup),
+    EditPosition,
+    Play(GameState),
+    Shapes(Vec<Shape>),
 }
 
 #[derive(Debug)]
 struct ChessApp {
-    history: Vec<GameState>,
-    current: usize,
+    tree: GameTree,
     flipped: bool,
     side: BoardRole,
+    clock: Clock,
+    premoves: Vec<ChessMove>,
+    eval: (Score, Color),
+    focus: Option<Focus>,
+    setup: Option<Setup>,
+    pieces: PieceSet,
+    sound: ChessBoardSound,
+    engine: Arc<Mutex<Searcher>>,
+}
+
+fn new_clock() -> Clock {
+    Clock::new(TimeControl::fischer(
+        Duration::from_secs(5 * 60),
+        Duration::from_secs(3),
+    ))
+}
+
+fn new_engine() -> Arc<Mutex<Searcher>> {
+    Arc::new(Mutex::new(Searcher::new(Level::default())))
+}
+
+fn load_pieces() -> PieceSet {
+    let Some(dir) = std::env::args().nth(1) else {
+        return PieceSet::default();
+    };
+    PieceSet::from_dir(&dir).unwrap_or_else(|error| {
+        eprintln!("{dir}: {error}");
+        PieceSet::default()
+    })
 }
 
 impl ChessApp {
     fn new() -> (Self, Task<Message>) {
         (
             Self {
-                history: vec![GameState::default()],
-                current: 0,
+                tree: GameTree::default(),
                 flipped: false,
                 side: BoardRole::Player(Color::White),
+                clock: new_clock(),
+                premoves: Vec::new(),
+                eval: (Score::Cp(0), Color::White),
+                focus: None,
+                setup: None,
+                pieces: load_pieces(),
+                sound: ChessBoardSound::new(),
+                engine: new_engine(),
             },
             Task::none(),
         )
@@ -59,92 +116,231 @@ impl ChessApp {
         Theme::Dark
     }
 
-    fn update(&mut self, message: self::Message) {
+    fn subscription(&self) -> Subscription<Message> {
+        clock::subscription(&self.clock).map(Message::Tick)
+    }
+
+    fn update(&mut self, message: self::Message) -> Task<Message> {
         match message {
             Message::OnMove(mv) => {
-                let state = self.history[self.current].make_move(mv);
+                let mover = self.tree.current().board.side_to_move();
+                self.tree.play(mv);
 
-                self.history.truncate(self.current + 1);
-                self.history.push(state);
-
-                self.current += 1;
+                let state = *self.tree.current();
+                if self.tree.is_mainline(self.tree.cursor()) {
+                    if self.clock.is_running() {
+                        if let Some(event) = self.clock.press() {
+                            self.on_clock(event);
+                        }
+                    } else {
+                        self.clock.start(!mover);
+                    }
+                    self.tree
+                        .set_outcome(GameOutcome::from_status(state.status()));
+                    if self.tree.is_over() {
+                        self.clock.pause();
+                    }
+                }
 
                 let color = state.board.side_to_move();
-                if !self.side.can_move(&color) && state.board.status() == BoardStatus::Ongoing {
-                    for mv in MoveGen::new_legal(&state.board) {
-                        self.update(Message::OnMove(mv));
-                        break;
+                if !self.side.can_move(&color) && !self.tree.is_over() {
+                    let node = self.tree.cursor();
+                    return engine::search(&self.engine, &self.tree)
+                        .map(move |result| Message::EngineMove(node, result));
+                }
+            }
+            Message::EngineMove(node, result) => {
+                if self.tree.cursor() == node && !self.tree.is_over() {
+                    let side = self.tree.current().board.side_to_move();
+                    self.eval = (result.score.unwrap_or(Score::Cp(0)), side);
+                    if let Some(mv) = result.best {
+                        return self.update(Message::OnMove(mv));
                     }
                 }
             }
-            Message::Flip => {
-                self.flipped = !self.flipped;
+            Message::OnPremove(premoves) => {
+                self.premoves = premoves;
             }
             Message::Restart => {
-                self.history = vec![GameState::default()];
-                self.current = 0;
+                self.tree = GameTree::default();
+                self.clock = new_clock();
+                self.engine = new_engine();
+                self.eval = (Score::Cp(0), Color::White);
             }
-            Message::Next => {
-                if self.current + 1 < self.history.len() {
-                    self.current += 1;
+            Message::Tick(_) => {
+                for event in self.clock.tick() {
+                    self.on_clock(event);
                 }
             }
-            Message::Back => {
-                if self.current > 0 {
-                    self.current -= 1;
-                }
+            Message::Focus(focus) => {
+                self.focus = focus;
+            }
+            Message::Edit(setup) => {
+                self.setup = Some(setup);
+            }
+            Message::EditPosition => {
+                self.setup = match self.setup {
+                    Some(_) => None,
+                    None => Some(Setup::from_state(self.tree.current())),
+                };
             }
-            Message::Set(current) => {
-                if current < self.history.len() {
-                    self.current = current;
+            Message::Play(game) => {
+                self.setup = None;
+                self.tree = GameTree::new(game);
+                self.clock = new_clock();
+                self.engine = new_engine();
+                self.eval = (Score::Cp(0), game.board.side_to_move());
+            }
+            Message::Shapes(shapes) => {
+                let node = self.tree.cursor();
+                let comment = &self.tree.node(node).unwrap().comment;
+                let comment = shape::write_comment(comment, &shapes);
+                self.tree.set_comment(node, comment);
+            }
+            Message::Select(node) => {
+                self.tree.go_to(node);
+            }
+            Message::Control(control) => match control {
+                Control::First => self.tree.go_start(),
+                Control::Previous => {
+                    self.tree.back();
                 }
+                Control::Next => {
+                    self.tree.forward();
+                }
+                Control::Last => self.tree.go_end(),
+                Control::Flip => self.flipped = !self.flipped,
+                Control::Resign => {
+                    if let BoardRole::Player(color) = self.side {
+                        self.tree.set_outcome(Some(GameOutcome::resignation(color)));
+                        self.clock.pause();
+                    }
+                }
+                Control::Takeback | Control::OfferDraw => {}
+            },
+        }
+
+        Task::none()
+    }
+
+    fn on_clock(&mut self, event: ClockEvent) {
+        match event {
+            ClockEvent::LowTime(_) => self.sound.play(SoundType::TenSeconds),
+            ClockEvent::Flag(flagged) => {
+                let state = *self.tree.current();
+                self.tree
+                    .set_outcome(Some(GameOutcome::timeout(flagged, &state)));
+                self.sound.play(SoundType::GameEnd);
             }
         }
     }
 
     fn view(&self) -> Element<'_, self::Message> {
-        let game = self.history[self.current];
+        let game = *self.tree.current();
         let color = game.board.side_to_move();
 
-        let can_go_back = self.current > 0;
-        let can_go_next = self.current + 1 < self.history.len();
+        let shapes = if *game.board.checkers() != EMPTY {
+            vec![
+                Shape::circle(
+                    game.board.king_square(color),
+                    iced::Color::from_rgb8(235, 97, 80),
+                )
+                .opacity(0.8),
+            ]
+        } else {
+            Vec::new()
+        };
 
-        let chessboard = ChessBoard::new(game, self.flipped).on_move_maybe(
-            if !can_go_next && self.side.can_move(&color) {
+        let chessboard = ChessBoard::new(game, self.flipped)
+            .piece_set(self.pieces.clone())
+            .shapes(shapes)
+            .drawings(shape::from_comment(
+                &self.tree.node(self.tree.cursor()).unwrap().comment,
+            ))
+            .on_shapes_changed(Message::Shapes)
+            .outcome(self.tree.outcome())
+            .role(self.side)
+            .chain_premoves(true)
+            .on_premove(Message::OnPremove)
+            .on_control(Message::Control)
+            .on_focus(Message::Focus)
+            .on_move_maybe(if self.side.can_move(&color) {
                 Some(Message::OnMove)
             } else {
                 None
-            },
-        );
+            });
+
+        let controls = GameControls::from_tree(&self.tree)
+            .resign(!self.tree.is_over())
+            .on_control(Message::Control);
 
         let manag = row![
-            button("|<").on_press_maybe(can_go_back.then_some(Message::Set(0))),
-            button("<").on_press_maybe(can_go_back.then_some(Message::Back)),
-            text(format!(
-                "{}{}",
-                if color == Color::White { "W" } else { "B" },
-                (self.current / 2) as usize
-            )),
-            button(">").on_press_maybe(can_go_next.then_some(Message::Next)),
-            button(">|")
-                .on_press_maybe(can_go_next.then_some(Message::Set(self.history.len() - 1))),
-            horizontal_space(),
-            text(format!(
-                "{:?} | {:?}",
-                game.board.status(),
-                game.board.side_to_move()
-            )),
+            text(match game.claimable_draw() {
+                Some(draw) => format!("{:?} | {:?} | {draw:?}", game.status(), color),
+                None => format!("{:?} | {:?}", game.status(), color),
+            }),
+            text(format!("premoves: {}", self.premoves.len())),
+            text(
+                self.focus
+                    .map(|focus| focus.to_string())
+                    .unwrap_or_default()
+            ),
             horizontal_space(),
-            button("Flip").on_press(Message::Flip),
+            button("Edit").on_press(Message::EditPosition),
             button("Restart").on_press(Message::Restart),
         ]
         .align_y(Alignment::Center)
         .spacing(10);
 
-        column![chessboard, manag]
-            .align_x(Alignment::Center)
+        if let Some(setup) = self.setup {
+            return column![
+                row![
+                    ChessBoard::editor(setup, self.flipped)
+                        .piece_set(self.pieces.clone())
+                        .on_edit(Message::Edit),
+                    SetupPanel::new(setup)
+                        .on_change(Message::Edit)
+                        .on_submit(Message::Play)
+                ]
+                .spacing(10),
+                manag
+            ]
             .spacing(10)
             .padding(10)
-            .into()
+            .into();
+        }
+
+        let (top, bottom) = if self.flipped {
+            (Color::White, Color::Black)
+        } else {
+            (Color::Black, Color::White)
+        };
+
+        column![
+            row![
+                CapturedPieces::from_tree(&self.tree, top).piece_set(self.pieces.clone()),
+                ChessClock::from_clock(&self.clock, top)
+            ]
+            .align_y(Alignment::Center),
+            row![
+                EvalBar::relative(self.eval.0, self.eval.1).flipped(self.flipped),
+                chessboard,
+                NotationPanel::new(&self.tree)
+                    .width(260)
+                    .on_select(Message::Select)
+            ]
+            .spacing(10),
+            row![
+                CapturedPieces::from_tree(&self.tree, bottom).piece_set(self.pieces.clone()),
+                ChessClock::from_clock(&self.clock, bottom)
+            ]
+            .align_y(Alignment::Center),
+            controls,
+            manag
+        ]
+        .align_x(Alignment::Center)
+        .spacing(10)
+        .padding(10)
+        .into()
     }
 }
@@ -1,4 +1,4 @@
-use chess::{Board, ChessMove, Color, Piece};
+use chess::{BitBoard, Board, BoardStatus, ChessMove, Color, EMPTY, MoveGen, Piece, Square};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum BoardRole {
@@ -17,16 +17,119 @@ impl BoardRole {
     }
 }
 
-#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct GameState {
     pub board: Board,
     pub annotation: Option<Annotation>,
+    pub halfmove: u32,
+    pub fullmove: u32,
+    pub repetitions: u32,
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        Self::new(Board::default())
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct Annotation {
     pub mv: ChessMove,
     pub kind: Move,
+    pub piece: Piece,
+    pub capture: Option<Piece>,
+    pub file_hint: bool,
+    pub rank_hint: bool,
+    pub check: bool,
+    pub mate: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DrawReason {
+    Stalemate,
+    InsufficientMaterial,
+    ThreefoldRepetition,
+    FivefoldRepetition,
+    FiftyMoveRule,
+    SeventyFiveMoveRule,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GameStatus {
+    Ongoing,
+    Checkmate(Color),
+    Draw(DrawReason),
+}
+
+impl GameStatus {
+    pub fn is_over(&self) -> bool {
+        *self != GameStatus::Ongoing
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Termination {
+    Checkmate,
+    Resignation,
+    Timeout,
+    DrawAgreement,
+    Abandonment,
+    Draw(DrawReason),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct GameOutcome {
+    pub winner: Option<Color>,
+    pub termination: Termination,
+}
+
+impl GameOutcome {
+    pub fn from_status(status: GameStatus) -> Option<Self> {
+        match status {
+            GameStatus::Ongoing => None,
+            GameStatus::Checkmate(winner) => Some(Self {
+                winner: Some(winner),
+                termination: Termination::Checkmate,
+            }),
+            GameStatus::Draw(reason) => Some(Self {
+                winner: None,
+                termination: Termination::Draw(reason),
+            }),
+        }
+    }
+
+    pub fn resignation(resigned: Color) -> Self {
+        Self {
+            winner: Some(!resigned),
+            termination: Termination::Resignation,
+        }
+    }
+
+    pub fn timeout(flagged: Color, state: &GameState) -> Self {
+        let winner = !flagged;
+        Self {
+            winner: (!state.has_insufficient_material(winner)).then_some(winner),
+            termination: Termination::Timeout,
+        }
+    }
+
+    pub fn draw_agreement() -> Self {
+        Self {
+            winner: None,
+            termination: Termination::DrawAgreement,
+        }
+    }
+
+    pub fn abandonment(abandoned: Color) -> Self {
+        Self {
+            winner: Some(!abandoned),
+            termination: Termination::Abandonment,
+        }
+    }
+
+    pub fn is_draw(&self) -> bool {
+        self.winner.is_none()
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -39,33 +142,478 @@ pub enum Move {
 }
 
 impl GameState {
+    pub fn new(board: Board) -> Self {
+        Self {
+            board,
+            annotation: None,
+            halfmove: 0,
+            fullmove: 1,
+            repetitions: 1,
+        }
+    }
+
+    pub fn status(&self) -> GameStatus {
+        match self.board.status() {
+            BoardStatus::Checkmate => GameStatus::Checkmate(!self.board.side_to_move()),
+            BoardStatus::Stalemate => GameStatus::Draw(DrawReason::Stalemate),
+            BoardStatus::Ongoing if self.is_dead_position() => {
+                GameStatus::Draw(DrawReason::InsufficientMaterial)
+            }
+            BoardStatus::Ongoing if self.repetitions >= 5 => {
+                GameStatus::Draw(DrawReason::FivefoldRepetition)
+            }
+            BoardStatus::Ongoing if self.halfmove >= 150 => {
+                GameStatus::Draw(DrawReason::SeventyFiveMoveRule)
+            }
+            BoardStatus::Ongoing => GameStatus::Ongoing,
+        }
+    }
+
+    pub fn claimable_draw(&self) -> Option<DrawReason> {
+        if self.status().is_over() {
+            None
+        } else if self.repetitions >= 3 {
+            Some(DrawReason::ThreefoldRepetition)
+        } else if self.halfmove >= 100 {
+            Some(DrawReason::FiftyMoveRule)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_dead_position(&self) -> bool {
+        self.has_insufficient_material(Color::White) && self.has_insufficient_material(Color::Black)
+    }
+
+    pub fn has_insufficient_material(&self, color: Color) -> bool {
+        let board = &self.board;
+        let ours = *board.color_combined(color);
+        let theirs = *board.color_combined(!color);
+        let heavy =
+            *board.pieces(Piece::Pawn) | *board.pieces(Piece::Rook) | *board.pieces(Piece::Queen);
+
+        if ours & heavy != EMPTY {
+            return false;
+        }
+
+        let knights = ours & *board.pieces(Piece::Knight);
+        let bishops = *board.pieces(Piece::Bishop);
+        let their_pieces = theirs & !*board.pieces(Piece::King);
+
+        if knights != EMPTY {
+            return knights.popcnt() == 1 && ours & bishops == EMPTY && their_pieces == EMPTY;
+        }
+
+        if ours & bishops != EMPTY {
+            const DARK: BitBoard = BitBoard(0xAA55_AA55_AA55_AA55);
+            let all_bishops = bishops & (ours | theirs);
+            let same_color = all_bishops & DARK == EMPTY || all_bishops & !DARK == EMPTY;
+            return same_color && their_pieces & !bishops == EMPTY;
+        }
+
+        true
+    }
+
     pub fn make_move(&self, mv: ChessMove) -> GameState {
         let board = self.board;
         let new_board = board.make_move_new(mv);
+        let annotation = self.annotate(mv, &new_board);
+
+        let halfmove = if annotation.piece == Piece::Pawn || annotation.capture.is_some() {
+            0
+        } else {
+            self.halfmove + 1
+        };
+
+        let fullmove = match board.side_to_move() {
+            Color::White => self.fullmove,
+            Color::Black => self.fullmove + 1,
+        };
+
+        GameState {
+            board: new_board,
+            annotation: Some(annotation),
+            halfmove,
+            fullmove,
+            repetitions: 1,
+        }
+    }
+
+    fn annotate(&self, mv: ChessMove, after: &Board) -> Annotation {
+        let board = self.board;
 
         let source = mv.get_source();
         let dest = mv.get_dest();
 
         let color = board.side_to_move();
-        let piece = board.piece_on(source);
+        let piece = board.piece_on(source).unwrap_or(Piece::Pawn);
 
         let kind = if mv.get_promotion().is_some() {
             Move::Promotion
         } else if board.piece_on(dest).is_some() {
             Move::Capture
-        } else if piece == Some(Piece::King)
+        } else if piece == Piece::King
             && (source.get_file().to_index() as i32 - dest.get_file().to_index() as i32).abs() == 2
         {
             Move::Castling
-        } else if piece == Some(Piece::Pawn) && board.en_passant() == dest.backward(color) {
+        } else if piece == Piece::Pawn && board.en_passant() == dest.backward(color) {
             Move::EnPassant
         } else {
             Move::Normal
         };
 
-        GameState {
-            board: new_board,
-            annotation: Some(Annotation { mv, kind }),
+        let capture = match kind {
+            Move::EnPassant => Some(Piece::Pawn),
+            _ => board.piece_on(dest),
+        };
+
+        let (mut file_hint, mut rank_hint) = (false, false);
+        if piece != Piece::Pawn && piece != Piece::King {
+            let rivals: Vec<Square> = MoveGen::new_legal(&board)
+                .filter(|other| {
+                    other.get_dest() == dest
+                        && other.get_source() != source
+                        && board.piece_on(other.get_source()) == Some(piece)
+                })
+                .map(|other| other.get_source())
+                .collect();
+
+            if !rivals.is_empty() {
+                let same_file = rivals.iter().any(|sq| sq.get_file() == source.get_file());
+                let same_rank = rivals.iter().any(|sq| sq.get_rank() == source.get_rank());
+                file_hint = !same_file || same_rank;
+                rank_hint = same_file;
+            }
+        }
+
+        Annotation {
+            mv,
+            kind,
+            piece,
+            capture,
+            file_hint,
+            rank_hint,
+            check: after.checkers().popcnt() > 0,
+            mate: after.status() == BoardStatus::Checkmate,
+        }
+    }
+}
+
+pub type NodeId = usize;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Node {
+    pub state: GameState,
+    pub parent: Option<NodeId>,
+    pub children: Vec<NodeId>,
+    pub starting_comment: String,
+    pub comment: String,
+    pub nags: Vec<u8>,
+}
+
+impl Node {
+    fn new(state: GameState, parent: Option<NodeId>) -> Self {
+        Self {
+            state,
+            parent,
+            children: Vec::new(),
+            starting_comment: String::new(),
+            comment: String::new(),
+            nags: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GameTree {
+    nodes: Vec<Option<Node>>,
+    cursor: NodeId,
+    outcome: Option<GameOutcome>,
+}
+
+impl Default for GameTree {
+    fn default() -> Self {
+        Self::new(GameState::default())
+    }
+}
+
+impl GameTree {
+    pub const ROOT: NodeId = 0;
+
+    pub fn new(root: GameState) -> Self {
+        Self {
+            nodes: vec![Some(Node::new(root, None))],
+            cursor: Self::ROOT,
+            outcome: None,
+        }
+    }
+
+    pub fn node(&self, id: NodeId) -> Option<&Node> {
+        self.nodes.get(id)?.as_ref()
+    }
+
+    fn node_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+        self.nodes.get_mut(id)?.as_mut()
+    }
+
+    pub fn set_comment(&mut self, id: NodeId, comment: impl Into<String>) -> bool {
+        self.node_mut(id)
+            .map(|node| node.comment = comment.into())
+            .is_some()
+    }
+
+    pub fn set_starting_comment(&mut self, id: NodeId, comment: impl Into<String>) -> bool {
+        self.node_mut(id)
+            .map(|node| node.starting_comment = comment.into())
+            .is_some()
+    }
+
+    pub fn set_nags(&mut self, id: NodeId, nags: Vec<u8>) -> bool {
+        self.node_mut(id).map(|node| node.nags = nags).is_some()
+    }
+
+    pub fn contains(&self, id: NodeId) -> bool {
+        self.node(id).is_some()
+    }
+
+    pub fn state(&self, id: NodeId) -> Option<&GameState> {
+        self.node(id).map(|node| &node.state)
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.node(id)?.parent
+    }
+
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        match self.node(id) {
+            Some(node) => &node.children,
+            None => &[],
+        }
+    }
+
+    pub fn cursor(&self) -> NodeId {
+        self.cursor
+    }
+
+    pub fn current(&self) -> &GameState {
+        &self.nodes[self.cursor].as_ref().unwrap().state
+    }
+
+    pub fn root(&self) -> &GameState {
+        &self.nodes[Self::ROOT].as_ref().unwrap().state
+    }
+
+    pub fn outcome(&self) -> Option<GameOutcome> {
+        self.outcome
+    }
+
+    pub fn set_outcome(&mut self, outcome: Option<GameOutcome>) {
+        self.outcome = outcome;
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    pub fn ply(&self, id: NodeId) -> usize {
+        self.path(id).len().saturating_sub(1)
+    }
+
+    pub fn path(&self, id: NodeId) -> Vec<NodeId> {
+        let mut path = Vec::new();
+        let mut node = self.contains(id).then_some(id);
+        while let Some(id) = node {
+            path.push(id);
+            node = self.parent(id);
+        }
+        path.reverse();
+        path
+    }
+
+    pub fn mainline(&self) -> Vec<NodeId> {
+        self.line_from(Self::ROOT)
+    }
+
+    pub fn line_from(&self, id: NodeId) -> Vec<NodeId> {
+        let mut line = Vec::new();
+        let mut node = self.contains(id).then_some(id);
+        while let Some(id) = node {
+            line.push(id);
+            node = self.children(id).first().copied();
+        }
+        line
+    }
+
+    pub fn is_mainline(&self, id: NodeId) -> bool {
+        self.path(id)
+            .windows(2)
+            .all(|pair| self.children(pair[0]).first() == Some(&pair[1]))
+    }
+
+    pub fn variations(&self, id: NodeId) -> &[NodeId] {
+        match self.parent(id) {
+            Some(parent) => self.children(parent),
+            None => &[],
+        }
+    }
+
+    pub fn find_child(&self, id: NodeId, mv: ChessMove) -> Option<NodeId> {
+        self.children(id).iter().copied().find(|&child| {
+            self.state(child)
+                .and_then(|state| state.annotation)
+                .is_some_and(|ant| ant.mv == mv)
+        })
+    }
+
+    pub fn add_move(&mut self, parent: NodeId, mv: ChessMove) -> Option<NodeId> {
+        if let Some(child) = self.find_child(parent, mv) {
+            return Some(child);
+        }
+
+        let mut state = self.state(parent)?.make_move(mv);
+        state.repetitions += self.repetitions_before(parent, &state);
+        let id = self.nodes.len();
+        self.nodes.push(Some(Node::new(state, Some(parent))));
+        self.node_mut(parent)?.children.push(id);
+
+        Some(id)
+    }
+
+    fn repetitions_before(&self, parent: NodeId, state: &GameState) -> u32 {
+        let hash = state.board.get_hash();
+        let mut count = 0;
+        let mut node = Some(parent);
+        let mut window = state.halfmove;
+
+        while let Some(id) = node {
+            if window == 0 {
+                break;
+            }
+            let ancestor = self.state(id).unwrap();
+            if ancestor.board.get_hash() == hash && ancestor.board == state.board {
+                count += 1;
+            }
+            window -= 1;
+            node = self.parent(id);
+        }
+        count
+    }
+
+    pub fn play(&mut self, mv: ChessMove) -> NodeId {
+        let id = self.add_move(self.cursor, mv).unwrap();
+        self.cursor = id;
+        id
+    }
+
+    pub fn go_to(&mut self, id: NodeId) -> bool {
+        if self.contains(id) {
+            self.cursor = id;
+        }
+        self.cursor == id
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        self.parent(self.cursor).is_some()
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        !self.children(self.cursor).is_empty()
+    }
+
+    pub fn back(&mut self) -> bool {
+        match self.parent(self.cursor) {
+            Some(parent) => self.go_to(parent),
+            None => false,
+        }
+    }
+
+    pub fn forward(&mut self) -> bool {
+        match self.children(self.cursor).first() {
+            Some(&child) => self.go_to(child),
+            None => false,
+        }
+    }
+
+    pub fn go_start(&mut self) {
+        self.cursor = Self::ROOT;
+    }
+
+    pub fn go_end(&mut self) {
+        while self.forward() {}
+    }
+
+    fn branch(&self, id: NodeId) -> Option<(NodeId, usize)> {
+        let mut node = id;
+        while let Some(parent) = self.parent(node) {
+            let children = self.children(parent);
+            if children.len() > 1 {
+                let index = children.iter().position(|&c| c == node)?;
+                return Some((parent, index));
+            }
+            node = parent;
+        }
+        None
+    }
+
+    pub fn promote_variation(&mut self, id: NodeId) -> bool {
+        match self.branch(id) {
+            Some((parent, index)) if index > 0 => {
+                self.node_mut(parent)
+                    .unwrap()
+                    .children
+                    .swap(index, index - 1);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn demote_variation(&mut self, id: NodeId) -> bool {
+        match self.branch(id) {
+            Some((parent, index)) if index + 1 < self.children(parent).len() => {
+                self.node_mut(parent)
+                    .unwrap()
+                    .children
+                    .swap(index, index + 1);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn promote_to_mainline(&mut self, id: NodeId) -> bool {
+        let path = self.path(id);
+        let mut changed = false;
+        for pair in path.windows(2) {
+            let children = &mut self.node_mut(pair[0]).unwrap().children;
+            if let Some(index) = children.iter().position(|&c| c == pair[1])
+                && index > 0
+            {
+                let child = children.remove(index);
+                children.insert(0, child);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    pub fn delete_variation(&mut self, id: NodeId) -> bool {
+        let Some(parent) = self.parent(id) else {
+            return false;
+        };
+
+        if self.path(self.cursor).contains(&id) {
+            self.cursor = parent;
+        }
+
+        self.node_mut(parent).unwrap().children.retain(|&c| c != id);
+
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            if let Some(node) = self.nodes[id].take() {
+                stack.extend(node.children);
+            }
         }
+        true
     }
 }
@@ -0,0 +1,361 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use chess::Color;
+use iced::Subscription;
+
+pub const LOW_TIME: Duration = Duration::from_secs(10);
+pub const TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+pub trait TimeSource: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemTime;
+
+impl TimeSource for SystemTime {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ManualTime {
+    start: Instant,
+    elapsed: Arc<Mutex<Duration>>,
+}
+
+impl Default for ManualTime {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ManualTime {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            elapsed: Arc::new(Mutex::new(Duration::ZERO)),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        if let Ok(mut elapsed) = self.elapsed.lock() {
+            *elapsed += duration;
+        }
+    }
+}
+
+impl TimeSource for ManualTime {
+    fn now(&self) -> Instant {
+        self.start
+            + self
+                .elapsed
+                .lock()
+                .map_or(Duration::ZERO, |elapsed| *elapsed)
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Increment {
+    #[default]
+    None,
+    Fischer(Duration),
+    Bronstein(Duration),
+    Delay(Duration),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Stage {
+    pub moves: Option<u32>,
+    pub time: Duration,
+    pub increment: Increment,
+}
+
+impl Stage {
+    pub fn new(time: Duration, increment: Increment) -> Self {
+        Self {
+            moves: None,
+            time,
+            increment,
+        }
+    }
+
+    #[must_use]
+    pub fn moves(mut self, moves: u32) -> Self {
+        self.moves = Some(moves);
+        self
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TimeControl {
+    pub stages: Vec<Stage>,
+}
+
+impl TimeControl {
+    pub fn new(time: Duration, increment: Increment) -> Self {
+        Self {
+            stages: vec![Stage::new(time, increment)],
+        }
+    }
+
+    pub fn from_stages(stages: Vec<Stage>) -> Option<Self> {
+        (!stages.is_empty()).then_some(Self { stages })
+    }
+
+    pub fn sudden_death(time: Duration) -> Self {
+        Self::new(time, Increment::None)
+    }
+
+    pub fn fischer(time: Duration, increment: Duration) -> Self {
+        Self::new(time, Increment::Fischer(increment))
+    }
+
+    pub fn bronstein(time: Duration, delay: Duration) -> Self {
+        Self::new(time, Increment::Bronstein(delay))
+    }
+
+    pub fn delay(time: Duration, delay: Duration) -> Self {
+        Self::new(time, Increment::Delay(delay))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ClockEvent {
+    LowTime(Color),
+    Flag(Color),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Side {
+    remaining: Duration,
+    stage: usize,
+    stage_moves: u32,
+    moves: u32,
+    warned: bool,
+}
+
+#[derive(Clone)]
+pub struct Clock {
+    controls: [TimeControl; 2],
+    sides: [Side; 2],
+    running: Option<Color>,
+    paused: Option<Color>,
+    turn: Duration,
+    since: Option<Instant>,
+    flagged: Option<Color>,
+    source: Arc<dyn TimeSource>,
+}
+
+impl fmt::Debug for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Clock")
+            .field("white", &self.remaining(Color::White))
+            .field("black", &self.remaining(Color::Black))
+            .field("running", &self.running)
+            .field("flagged", &self.flagged)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Clock {
+    pub fn new(control: TimeControl) -> Self {
+        Self::with_odds(control.clone(), control)
+    }
+
+    pub fn with_odds(white: TimeControl, black: TimeControl) -> Self {
+        let side = |control: &TimeControl| Side {
+            remaining: control.stages[0].time,
+            stage: 0,
+            stage_moves: 0,
+            moves: 0,
+            warned: false,
+        };
+
+        Self {
+            sides: [side(&white), side(&black)],
+            controls: [white, black],
+            running: None,
+            paused: None,
+            turn: Duration::ZERO,
+            since: None,
+            flagged: None,
+            source: Arc::new(SystemTime),
+        }
+    }
+
+    #[must_use]
+    pub fn source(mut self, source: impl TimeSource + 'static) -> Self {
+        self.source = Arc::new(source);
+        self
+    }
+
+    pub fn control(&self, color: Color) -> &TimeControl {
+        &self.controls[color.to_index()]
+    }
+
+    pub fn running(&self) -> Option<Color> {
+        self.running
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.is_some()
+    }
+
+    pub fn flagged(&self) -> Option<Color> {
+        self.flagged
+    }
+
+    pub fn moves(&self, color: Color) -> u32 {
+        self.sides[color.to_index()].moves
+    }
+
+    pub fn stage(&self, color: Color) -> usize {
+        self.sides[color.to_index()].stage
+    }
+
+    pub fn moves_to_go(&self, color: Color) -> Option<u32> {
+        let side = &self.sides[color.to_index()];
+        let moves = self.controls[color.to_index()].stages[side.stage].moves?;
+        Some(moves - side.stage_moves)
+    }
+
+    fn increment(&self, color: Color) -> Increment {
+        let side = &self.sides[color.to_index()];
+        self.controls[color.to_index()].stages[side.stage].increment
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.turn
+            + self
+                .since
+                .map_or(Duration::ZERO, |since| self.source.now() - since)
+    }
+
+    pub fn remaining(&self, color: Color) -> Duration {
+        let remaining = self.sides[color.to_index()].remaining;
+        if self.running != Some(color) && self.paused != Some(color) {
+            return remaining;
+        }
+
+        self.remaining_after(color, self.elapsed())
+    }
+
+    fn remaining_after(&self, color: Color, elapsed: Duration) -> Duration {
+        let remaining = self.sides[color.to_index()].remaining;
+        match self.increment(color) {
+            Increment::Delay(delay) => remaining.saturating_sub(elapsed.saturating_sub(delay)),
+            _ => remaining.saturating_sub(elapsed),
+        }
+    }
+
+    pub fn start(&mut self, color: Color) {
+        if self.flagged.is_some() {
+            return;
+        }
+        self.running = Some(color);
+        self.paused = None;
+        self.turn = Duration::ZERO;
+        self.since = Some(self.source.now());
+    }
+
+    pub fn press(&mut self) -> Option<ClockEvent> {
+        let color = self.running?;
+        let elapsed = self.elapsed();
+        if let Some(event) = self.check_flag(color, self.remaining_after(color, elapsed)) {
+            return Some(event);
+        }
+
+        let increment = self.increment(color);
+        let controls = &self.controls[color.to_index()];
+        let side = &mut self.sides[color.to_index()];
+
+        side.remaining = match increment {
+            Increment::None => side.remaining.saturating_sub(elapsed),
+            Increment::Fischer(bonus) => side.remaining.saturating_sub(elapsed) + bonus,
+            Increment::Bronstein(delay) => {
+                side.remaining.saturating_sub(elapsed) + elapsed.min(delay)
+            }
+            Increment::Delay(delay) => side.remaining.saturating_sub(elapsed.saturating_sub(delay)),
+        };
+
+        side.moves += 1;
+        side.stage_moves += 1;
+        if controls.stages[side.stage].moves == Some(side.stage_moves) {
+            side.stage = (side.stage + 1).min(controls.stages.len() - 1);
+            side.stage_moves = 0;
+            side.remaining += controls.stages[side.stage].time;
+            side.warned = false;
+        }
+
+        self.start(!color);
+        None
+    }
+
+    pub fn pause(&mut self) {
+        if let Some(color) = self.running.take() {
+            self.turn = self.elapsed();
+            self.since = None;
+            self.paused = Some(color);
+        }
+    }
+
+    pub fn resume(&mut self) {
+        if let Some(color) = self.paused.take() {
+            self.running = Some(color);
+            self.since = Some(self.source.now());
+        }
+    }
+
+    pub fn add_time(&mut self, color: Color, time: Duration) {
+        let side = &mut self.sides[color.to_index()];
+        side.remaining += time;
+        if side.remaining > LOW_TIME {
+            side.warned = false;
+        }
+    }
+
+    pub fn tick(&mut self) -> Vec<ClockEvent> {
+        let Some(color) = self.running else {
+            return Vec::new();
+        };
+
+        let remaining = self.remaining_after(color, self.elapsed());
+        if let Some(event) = self.check_flag(color, remaining) {
+            return vec![event];
+        }
+
+        let side = &mut self.sides[color.to_index()];
+        if remaining <= LOW_TIME && !side.warned {
+            side.warned = true;
+            return vec![ClockEvent::LowTime(color)];
+        }
+
+        Vec::new()
+    }
+
+    fn check_flag(&mut self, color: Color, remaining: Duration) -> Option<ClockEvent> {
+        if remaining > Duration::ZERO {
+            return None;
+        }
+
+        self.sides[color.to_index()].remaining = Duration::ZERO;
+        self.running = None;
+        self.since = None;
+        self.turn = Duration::ZERO;
+        self.flagged = Some(color);
+
+        Some(ClockEvent::Flag(color))
+    }
+}
+
+pub fn subscription(clock: &Clock) -> Subscription<Instant> {
+    if clock.is_running() {
+        iced::time::every(TICK_INTERVAL)
+    } else {
+        Subscription::none()
+    }
+}
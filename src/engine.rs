@@ -0,0 +1,580 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use chess::{BitBoard, Board, ChessMove, Color, EMPTY, MoveGen, Piece, Square};
+use futures::channel::oneshot;
+use iced::Task;
+
+use crate::chess::{GameState, GameTree};
+use crate::uci::Score;
+
+const INFINITY: i32 = 32_000;
+const MATE: i32 = 30_000;
+const MATE_BOUND: i32 = MATE - 1_000;
+const MAX_PLY: usize = 64;
+const MAX_TABLE_BITS: u8 = 18;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Level {
+    pub depth: u8,
+    pub time: Duration,
+    pub randomness: i32,
+}
+
+pub const LEVELS: [Level; 8] = [
+    Level::new(1, Duration::from_millis(100), 200),
+    Level::new(2, Duration::from_millis(200), 120),
+    Level::new(3, Duration::from_millis(300), 70),
+    Level::new(4, Duration::from_millis(500), 40),
+    Level::new(5, Duration::from_millis(1000), 20),
+    Level::new(6, Duration::from_millis(1500), 8),
+    Level::new(8, Duration::from_millis(2500), 0),
+    Level::new(MAX_PLY as u8, Duration::from_millis(5000), 0),
+];
+
+impl Level {
+    pub const fn new(depth: u8, time: Duration, randomness: i32) -> Self {
+        Self {
+            depth,
+            time,
+            randomness,
+        }
+    }
+
+    pub fn strength(strength: u8) -> Self {
+        LEVELS[(strength.clamp(1, LEVELS.len() as u8) - 1) as usize]
+    }
+
+    fn table_size(&self) -> usize {
+        1 << (12 + self.depth).min(MAX_TABLE_BITS)
+    }
+}
+
+impl Default for Level {
+    fn default() -> Self {
+        Self::strength(4)
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct SearchResult {
+    pub best: Option<ChessMove>,
+    pub score: Option<Score>,
+    pub depth: u8,
+    pub nodes: u64,
+    pub pv: Vec<ChessMove>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Bound {
+    Exact,
+    Lower,
+    Upper,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    key: u64,
+    depth: u8,
+    score: i32,
+    bound: Bound,
+    mv: Option<ChessMove>,
+}
+
+pub struct Searcher {
+    level: Level,
+    table: Vec<Option<Entry>>,
+    history: Vec<u64>,
+    nodes: u64,
+    deadline: Instant,
+    can_stop: bool,
+    stopped: bool,
+    rng: u64,
+}
+
+impl fmt::Debug for Searcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Searcher")
+            .field("level", &self.level)
+            .field("nodes", &self.nodes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Searcher {
+    pub fn new(level: Level) -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_nanos() as u64);
+
+        Self {
+            level,
+            table: vec![None; level.table_size()],
+            history: Vec::new(),
+            nodes: 0,
+            deadline: Instant::now(),
+            can_stop: false,
+            stopped: false,
+            rng: seed | 1,
+        }
+    }
+
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.rng = seed | 1;
+        self
+    }
+
+    pub fn search(&mut self, state: &GameState, history: &[u64]) -> SearchResult {
+        let board = state.board;
+        self.history = history.to_vec();
+        self.nodes = 0;
+        self.stopped = false;
+        self.deadline = Instant::now() + self.level.time;
+
+        let mut moves: Vec<(ChessMove, i32)> = self
+            .order(&board, None)
+            .into_iter()
+            .map(|mv| (mv, -INFINITY))
+            .collect();
+        if moves.is_empty() || state.status().is_over() {
+            return SearchResult::default();
+        }
+
+        let mut result = SearchResult::default();
+        for depth in 1..=self.level.depth.max(1) {
+            self.can_stop = depth > 1;
+            let Some(scored) = self.root(&board, depth, &moves) else {
+                break;
+            };
+
+            moves = scored;
+            moves.sort_by_key(|&(_, score)| -score);
+            result.depth = depth;
+            result.score = Some(to_score(moves[0].1));
+            result.pv = self.principal_variation(&board, moves[0].0, depth);
+
+            if moves[0].1.abs() >= MATE_BOUND || Instant::now() >= self.deadline {
+                break;
+            }
+        }
+
+        result.best = Some(self.pick(&moves));
+        result.nodes = self.nodes;
+        result
+    }
+
+    fn root(
+        &mut self,
+        board: &Board,
+        depth: u8,
+        moves: &[(ChessMove, i32)],
+    ) -> Option<Vec<(ChessMove, i32)>> {
+        let margin = self.level.randomness.max(0);
+        let mut alpha = -INFINITY;
+        let mut scored = Vec::with_capacity(moves.len());
+
+        self.history.push(board.get_hash());
+        for &(mv, _) in moves {
+            let child = board.make_move_new(mv);
+            let floor = (alpha - margin).max(-INFINITY);
+            let score = -self.negamax(&child, depth - 1, 1, -INFINITY, -floor);
+            if self.stopped {
+                self.history.pop();
+                return None;
+            }
+            alpha = alpha.max(score);
+            scored.push((mv, score));
+        }
+        self.history.pop();
+
+        Some(scored)
+    }
+
+    fn pick(&mut self, moves: &[(ChessMove, i32)]) -> ChessMove {
+        let best = moves[0].1;
+        if self.level.randomness <= 0 || best.abs() >= MATE_BOUND {
+            return moves[0].0;
+        }
+
+        let candidates: Vec<ChessMove> = moves
+            .iter()
+            .filter(|&&(_, score)| score >= best - self.level.randomness)
+            .map(|&(mv, _)| mv)
+            .collect();
+        candidates[(self.random() % candidates.len() as u64) as usize]
+    }
+
+    fn random(&mut self) -> u64 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        self.rng
+    }
+
+    fn expired(&mut self) -> bool {
+        if self.can_stop && self.nodes & 2047 == 0 && Instant::now() >= self.deadline {
+            self.stopped = true;
+        }
+        self.stopped
+    }
+
+    fn negamax(&mut self, board: &Board, depth: u8, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        self.nodes += 1;
+        if self.expired() {
+            return 0;
+        }
+
+        let hash = board.get_hash();
+        if self.history.contains(&hash) {
+            return 0;
+        }
+
+        let in_check = *board.checkers() != EMPTY;
+        let depth = if in_check && ply < MAX_PLY {
+            depth + 1
+        } else {
+            depth
+        };
+        if depth == 0 || ply >= MAX_PLY {
+            return self.quiesce(board, ply, alpha, beta);
+        }
+
+        let entry = self.probe(hash);
+        if let Some(entry) = entry
+            && entry.depth >= depth
+        {
+            let score = from_table(entry.score, ply);
+            match entry.bound {
+                Bound::Exact => return score,
+                Bound::Lower if score >= beta => return score,
+                Bound::Upper if score <= alpha => return score,
+                _ => {}
+            }
+        }
+
+        let moves = self.order(board, entry.and_then(|entry| entry.mv));
+        if moves.is_empty() {
+            return if in_check { -MATE + ply as i32 } else { 0 };
+        }
+
+        let original = alpha;
+        let mut best = -INFINITY;
+        let mut best_move = None;
+
+        self.history.push(hash);
+        for mv in moves {
+            let child = board.make_move_new(mv);
+            let score = -self.negamax(&child, depth - 1, ply + 1, -beta, -alpha);
+            if self.stopped {
+                self.history.pop();
+                return 0;
+            }
+
+            if score > best {
+                best = score;
+                best_move = Some(mv);
+            }
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+        self.history.pop();
+
+        let bound = if best <= original {
+            Bound::Upper
+        } else if best >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.store(hash, depth, to_table(best, ply), bound, best_move);
+
+        best
+    }
+
+    fn quiesce(&mut self, board: &Board, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        self.nodes += 1;
+        if self.expired() {
+            return 0;
+        }
+
+        let stand_pat = evaluate(board);
+        if stand_pat >= beta || ply >= MAX_PLY * 2 {
+            return stand_pat;
+        }
+        alpha = alpha.max(stand_pat);
+
+        let mut moves = MoveGen::new_legal(board);
+        moves.set_iterator_mask(*board.color_combined(!board.side_to_move()));
+        let mut captures: Vec<ChessMove> = moves.collect();
+        captures.sort_by_key(|&mv| -capture_order(board, mv));
+
+        for mv in captures {
+            let child = board.make_move_new(mv);
+            let score = -self.quiesce(&child, ply + 1, -beta, -alpha);
+            if self.stopped {
+                return 0;
+            }
+            if score >= beta {
+                return score;
+            }
+            alpha = alpha.max(score);
+        }
+
+        alpha
+    }
+
+    fn order(&self, board: &Board, first: Option<ChessMove>) -> Vec<ChessMove> {
+        let mut moves: Vec<ChessMove> = MoveGen::new_legal(board).collect();
+        moves.sort_by_key(|&mv| {
+            if Some(mv) == first {
+                i32::MIN
+            } else {
+                -capture_order(board, mv)
+            }
+        });
+        moves
+    }
+
+    fn probe(&self, hash: u64) -> Option<Entry> {
+        self.table[hash as usize % self.table.len()].filter(|entry| entry.key == hash)
+    }
+
+    fn store(&mut self, key: u64, depth: u8, score: i32, bound: Bound, mv: Option<ChessMove>) {
+        let len = self.table.len();
+        let slot = &mut self.table[key as usize % len];
+        if slot.is_none_or(|entry| entry.key != key || entry.depth <= depth) {
+            *slot = Some(Entry {
+                key,
+                depth,
+                score,
+                bound,
+                mv,
+            });
+        }
+    }
+
+    fn principal_variation(&self, board: &Board, first: ChessMove, depth: u8) -> Vec<ChessMove> {
+        let mut pv = vec![first];
+        let mut board = board.make_move_new(first);
+        while pv.len() < depth as usize {
+            let Some(mv) = self.probe(board.get_hash()).and_then(|entry| entry.mv) else {
+                break;
+            };
+            if !board.legal(mv) {
+                break;
+            }
+            pv.push(mv);
+            board = board.make_move_new(mv);
+        }
+        pv
+    }
+}
+
+pub fn history(tree: &GameTree) -> Vec<u64> {
+    let path = tree.path(tree.cursor());
+    let halfmove = tree.current().halfmove as usize;
+    path[..path.len() - 1]
+        .iter()
+        .rev()
+        .take(halfmove)
+        .filter_map(|&id| tree.state(id))
+        .map(|state| state.board.get_hash())
+        .collect()
+}
+
+pub async fn think(
+    searcher: Arc<Mutex<Searcher>>,
+    state: GameState,
+    history: Vec<u64>,
+) -> SearchResult {
+    let (sender, receiver) = oneshot::channel();
+    std::thread::spawn(move || {
+        let result = searcher
+            .lock()
+            .map(|mut searcher| searcher.search(&state, &history))
+            .unwrap_or_default();
+        let _ = sender.send(result);
+    });
+    receiver.await.unwrap_or_default()
+}
+
+pub fn search(searcher: &Arc<Mutex<Searcher>>, tree: &GameTree) -> Task<SearchResult> {
+    Task::perform(
+        think(searcher.clone(), *tree.current(), history(tree)),
+        |result| result,
+    )
+}
+
+fn to_score(score: i32) -> Score {
+    if score >= MATE_BOUND {
+        Score::Mate((MATE - score + 1) / 2)
+    } else if score <= -MATE_BOUND {
+        Score::Mate(-(MATE + score + 1) / 2)
+    } else {
+        Score::Cp(score)
+    }
+}
+
+fn to_table(score: i32, ply: usize) -> i32 {
+    match score {
+        s if s >= MATE_BOUND => s + ply as i32,
+        s if s <= -MATE_BOUND => s - ply as i32,
+        s => s,
+    }
+}
+
+fn from_table(score: i32, ply: usize) -> i32 {
+    match score {
+        s if s >= MATE_BOUND => s - ply as i32,
+        s if s <= -MATE_BOUND => s + ply as i32,
+        s => s,
+    }
+}
+
+const VALUES: [i32; 6] = [100, 320, 330, 500, 900, 0];
+
+fn capture_order(board: &Board, mv: ChessMove) -> i32 {
+    let promotion = mv
+        .get_promotion()
+        .map_or(0, |piece| VALUES[piece.to_index()]);
+    let attacker = board.piece_on(mv.get_source());
+    let en_passant = attacker == Some(Piece::Pawn)
+        && board.en_passant().is_some()
+        && board.en_passant() == mv.get_dest().backward(board.side_to_move());
+    let victim = match board.piece_on(mv.get_dest()) {
+        Some(piece) => VALUES[piece.to_index()],
+        None if en_passant => VALUES[Piece::Pawn.to_index()],
+        None => return promotion,
+    };
+    let attacker = attacker.map_or(0, |piece| VALUES[piece.to_index()]);
+    promotion + victim * 10 - attacker / 10 + 1
+}
+
+#[rustfmt::skip]
+const PAWN: [i32; 64] = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+    50, 50, 50, 50, 50, 50, 50, 50,
+    10, 10, 20, 30, 30, 20, 10, 10,
+     5,  5, 10, 25, 25, 10,  5,  5,
+     0,  0,  0, 20, 20,  0,  0,  0,
+     5, -5,-10,  0,  0,-10, -5,  5,
+     5, 10, 10,-20,-20, 10, 10,  5,
+     0,  0,  0,  0,  0,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const KNIGHT: [i32; 64] = [
+    -50,-40,-30,-30,-30,-30,-40,-50,
+    -40,-20,  0,  0,  0,  0,-20,-40,
+    -30,  0, 10, 15, 15, 10,  0,-30,
+    -30,  5, 15, 20, 20, 15,  5,-30,
+    -30,  0, 15, 20, 20, 15,  0,-30,
+    -30,  5, 10, 15, 15, 10,  5,-30,
+    -40,-20,  0,  5,  5,  0,-20,-40,
+    -50,-40,-30,-30,-30,-30,-40,-50,
+];
+
+#[rustfmt::skip]
+const BISHOP: [i32; 64] = [
+    -20,-10,-10,-10,-10,-10,-10,-20,
+    -10,  0,  0,  0,  0,  0,  0,-10,
+    -10,  0,  5, 10, 10,  5,  0,-10,
+    -10,  5,  5, 10, 10,  5,  5,-10,
+    -10,  0, 10, 10, 10, 10,  0,-10,
+    -10, 10, 10, 10, 10, 10, 10,-10,
+    -10,  5,  0,  0,  0,  0,  5,-10,
+    -20,-10,-10,-10,-10,-10,-10,-20,
+];
+
+#[rustfmt::skip]
+const ROOK: [i32; 64] = [
+     0,  0,  0,  0,  0,  0,  0,  0,
+     5, 10, 10, 10, 10, 10, 10,  5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+    -5,  0,  0,  0,  0,  0,  0, -5,
+     0,  0,  0,  5,  5,  0,  0,  0,
+];
+
+#[rustfmt::skip]
+const QUEEN: [i32; 64] = [
+    -20,-10,-10, -5, -5,-10,-10,-20,
+    -10,  0,  0,  0,  0,  0,  0,-10,
+    -10,  0,  5,  5,  5,  5,  0,-10,
+     -5,  0,  5,  5,  5,  5,  0, -5,
+      0,  0,  5,  5,  5,  5,  0, -5,
+    -10,  5,  5,  5,  5,  5,  0,-10,
+    -10,  0,  5,  0,  0,  0,  0,-10,
+    -20,-10,-10, -5, -5,-10,-10,-20,
+];
+
+#[rustfmt::skip]
+const KING_MIDDLE: [i32; 64] = [
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -30,-40,-40,-50,-50,-40,-40,-30,
+    -20,-30,-30,-40,-40,-30,-30,-20,
+    -10,-20,-20,-20,-20,-20,-20,-10,
+     20, 20,  0,  0,  0,  0, 20, 20,
+     20, 30, 10,  0,  0, 10, 30, 20,
+];
+
+#[rustfmt::skip]
+const KING_END: [i32; 64] = [
+    -50,-40,-30,-20,-20,-30,-40,-50,
+    -30,-20,-10,  0,  0,-10,-20,-30,
+    -30,-10, 20, 30, 30, 20,-10,-30,
+    -30,-10, 30, 40, 40, 30,-10,-30,
+    -30,-10, 30, 40, 40, 30,-10,-30,
+    -30,-10, 20, 30, 30, 20,-10,-30,
+    -30,-30,  0,  0,  0,  0,-30,-30,
+    -50,-30,-30,-30,-30,-30,-30,-50,
+];
+
+pub fn evaluate(board: &Board) -> i32 {
+    let heavy = (*board.pieces(Piece::Queen)
+        | *board.pieces(Piece::Rook)
+        | *board.pieces(Piece::Knight)
+        | *board.pieces(Piece::Bishop))
+    .popcnt();
+    let endgame = heavy <= 4 || (*board.pieces(Piece::Queen) == EMPTY && heavy <= 6);
+
+    let mut score = 0;
+    for color in [Color::White, Color::Black] {
+        let sign = if color == Color::White { 1 } else { -1 };
+        for piece in chess::ALL_PIECES {
+            let table = match piece {
+                Piece::Pawn => &PAWN,
+                Piece::Knight => &KNIGHT,
+                Piece::Bishop => &BISHOP,
+                Piece::Rook => &ROOK,
+                Piece::Queen => &QUEEN,
+                Piece::King if endgame => &KING_END,
+                Piece::King => &KING_MIDDLE,
+            };
+            let pieces: BitBoard = *board.pieces(piece) & *board.color_combined(color);
+            for square in pieces {
+                score += sign * (VALUES[piece.to_index()] + table[table_index(square, color)]);
+            }
+        }
+    }
+
+    match board.side_to_move() {
+        Color::White => score,
+        Color::Black => -score,
+    }
+}
+
+fn table_index(square: Square, color: Color) -> usize {
+    match color {
+        Color::White => square.to_index() ^ 56,
+        Color::Black => square.to_index(),
+    }
+}
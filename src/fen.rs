@@ -0,0 +1,328 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chess::{
+    ALL_SQUARES, BitBoard, Board, BoardBuilder, CastleRights, Color, EMPTY, File, Piece, Rank,
+    Square, get_bishop_moves, get_king_moves, get_knight_moves, get_pawn_attacks, get_rook_moves,
+};
+
+use crate::chess::GameState;
+use crate::notation::{char_file, char_piece, char_rank, piece_char};
+
+pub const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FenError {
+    FieldCount(usize),
+    RankCount(usize),
+    RankLength(Rank),
+    InvalidPiece(char),
+    InvalidSideToMove(String),
+    InvalidCastling(String),
+    InvalidEnPassant(String),
+    InvalidHalfmoveClock(String),
+    InvalidFullmoveNumber(String),
+    MissingKing(Color),
+    TooManyKings(Color),
+    PawnOnBackRank(Square),
+    InactiveSideInCheck(Color),
+    ImpossibleCastling(Color),
+    ImpossibleEnPassant(Square),
+    InvalidPosition,
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FenError::FieldCount(count) => write!(f, "expected 4 to 6 fields, found {count}"),
+            FenError::RankCount(count) => write!(f, "expected 8 ranks, found {count}"),
+            FenError::RankLength(rank) => {
+                write!(f, "rank {} does not have 8 squares", rank.to_index() + 1)
+            }
+            FenError::InvalidPiece(c) => write!(f, "invalid piece {c:?}"),
+            FenError::InvalidSideToMove(side) => write!(f, "invalid side to move {side:?}"),
+            FenError::InvalidCastling(castling) => write!(f, "invalid castling field {castling:?}"),
+            FenError::InvalidEnPassant(ep) => write!(f, "invalid en passant square {ep:?}"),
+            FenError::InvalidHalfmoveClock(clock) => write!(f, "invalid halfmove clock {clock:?}"),
+            FenError::InvalidFullmoveNumber(number) => {
+                write!(f, "invalid fullmove number {number:?}")
+            }
+            FenError::MissingKing(color) => write!(f, "{color:?} has no king"),
+            FenError::TooManyKings(color) => write!(f, "{color:?} has more than one king"),
+            FenError::PawnOnBackRank(square) => write!(f, "pawn on back rank at {square}"),
+            FenError::InactiveSideInCheck(color) => {
+                write!(f, "{color:?} is in check but it is not their move")
+            }
+            FenError::ImpossibleCastling(color) => {
+                write!(f, "{color:?} castling rights do not match king and rooks")
+            }
+            FenError::ImpossibleEnPassant(square) => {
+                write!(f, "en passant square {square} is not possible")
+            }
+            FenError::InvalidPosition => write!(f, "invalid position"),
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+impl GameState {
+    pub fn from_fen(fen: &str) -> Result<Self, FenError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        if !(4..=6).contains(&fields.len()) {
+            return Err(FenError::FieldCount(fields.len()));
+        }
+
+        let mut builder = BoardBuilder::new();
+        let mut pieces = [[EMPTY; 6]; 2];
+
+        let ranks: Vec<&str> = fields[0].split('/').collect();
+        if ranks.len() != 8 {
+            return Err(FenError::RankCount(ranks.len()));
+        }
+        for (row, placement) in ranks.iter().enumerate() {
+            let rank = Rank::from_index(7 - row);
+            let mut file = 0;
+            for c in placement.chars() {
+                if let Some(skip) = c.to_digit(10).filter(|d| (1..=8).contains(d)) {
+                    file += skip as usize;
+                } else {
+                    let piece = char_piece(c).ok_or(FenError::InvalidPiece(c))?;
+                    let color = if c.is_ascii_uppercase() {
+                        Color::White
+                    } else {
+                        Color::Black
+                    };
+                    if file >= 8 {
+                        return Err(FenError::RankLength(rank));
+                    }
+                    let square = Square::make_square(rank, File::from_index(file));
+                    builder.piece(square, piece, color);
+                    pieces[color.to_index()][piece.to_index()] |= BitBoard::from_square(square);
+                    file += 1;
+                }
+            }
+            if file != 8 {
+                return Err(FenError::RankLength(rank));
+            }
+        }
+
+        let side = match fields[1] {
+            "w" => Color::White,
+            "b" => Color::Black,
+            other => return Err(FenError::InvalidSideToMove(other.to_string())),
+        };
+        builder.side_to_move(side);
+
+        let mut rights = [CastleRights::NoRights; 2];
+        if fields[2] != "-" {
+            for c in fields[2].chars() {
+                let (color, right) = match c {
+                    'K' => (Color::White, CastleRights::KingSide),
+                    'Q' => (Color::White, CastleRights::QueenSide),
+                    'k' => (Color::Black, CastleRights::KingSide),
+                    'q' => (Color::Black, CastleRights::QueenSide),
+                    _ => return Err(FenError::InvalidCastling(fields[2].to_string())),
+                };
+                rights[color.to_index()] = rights[color.to_index()].add(right);
+            }
+        }
+        builder.castle_rights(Color::White, rights[Color::White.to_index()]);
+        builder.castle_rights(Color::Black, rights[Color::Black.to_index()]);
+
+        let en_passant = match fields[3] {
+            "-" => None,
+            ep => {
+                let mut chars = ep.chars();
+                let square = match (
+                    chars.next().and_then(char_file),
+                    chars.next().and_then(char_rank),
+                ) {
+                    (Some(file), Some(rank)) if chars.next().is_none() => {
+                        Square::make_square(rank, file)
+                    }
+                    _ => return Err(FenError::InvalidEnPassant(ep.to_string())),
+                };
+                Some(square)
+            }
+        };
+
+        let halfmove = match fields.get(4) {
+            Some(clock) => clock
+                .parse::<u32>()
+                .map_err(|_| FenError::InvalidHalfmoveClock(clock.to_string()))?,
+            None => 0,
+        };
+        let fullmove = match fields.get(5) {
+            Some(number) => number
+                .parse::<u32>()
+                .ok()
+                .filter(|&n| n > 0)
+                .ok_or_else(|| FenError::InvalidFullmoveNumber(number.to_string()))?,
+            None => 1,
+        };
+
+        validate(&pieces, side, rights, en_passant)?;
+
+        if let Some(square) = en_passant {
+            builder.en_passant(Some(square.get_file()));
+        }
+
+        let board = Board::try_from(&builder).map_err(|_| FenError::InvalidPosition)?;
+
+        Ok(GameState {
+            halfmove,
+            fullmove,
+            ..GameState::new(board)
+        })
+    }
+
+    pub fn to_fen(&self) -> String {
+        let board = &self.board;
+        let pieces = ALL_SQUARES.map(|square| board.piece_on(square).zip(board.color_on(square)));
+        let mut fen = placement(&pieces);
+
+        let side = board.side_to_move();
+        fen.push_str(match side {
+            Color::White => " w ",
+            Color::Black => " b ",
+        });
+
+        let castling = format!(
+            "{}{}",
+            board.castle_rights(Color::White).to_string(Color::White),
+            board.castle_rights(Color::Black).to_string(Color::Black)
+        );
+        fen.push_str(if castling.is_empty() { "-" } else { &castling });
+
+        match board.en_passant().and_then(|sq| sq.forward(side)) {
+            Some(square) => fen.push_str(&format!(" {square}")),
+            None => fen.push_str(" -"),
+        }
+
+        fen.push_str(&format!(" {} {}", self.halfmove, self.fullmove));
+        fen
+    }
+}
+
+impl FromStr for GameState {
+    type Err = FenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        GameState::from_fen(s)
+    }
+}
+
+pub(crate) fn placement(pieces: &[Option<(Piece, Color)>; 64]) -> String {
+    let mut fen = String::new();
+
+    for row in (0..8).rev() {
+        let mut empty = 0;
+        for col in 0..8 {
+            let square = Square::make_square(Rank::from_index(row), File::from_index(col));
+            match pieces[square.to_index()] {
+                Some((piece, color)) => {
+                    if empty > 0 {
+                        fen.push_str(&empty.to_string());
+                        empty = 0;
+                    }
+                    let c = piece_char(piece);
+                    fen.push(match color {
+                        Color::White => c,
+                        Color::Black => c.to_ascii_lowercase(),
+                    });
+                }
+                None => empty += 1,
+            }
+        }
+        if empty > 0 {
+            fen.push_str(&empty.to_string());
+        }
+        if row > 0 {
+            fen.push('/');
+        }
+    }
+
+    fen
+}
+
+fn validate(
+    pieces: &[[BitBoard; 6]; 2],
+    side: Color,
+    rights: [CastleRights; 2],
+    en_passant: Option<Square>,
+) -> Result<(), FenError> {
+    let color_bb = |color: Color| -> BitBoard {
+        pieces[color.to_index()]
+            .iter()
+            .fold(EMPTY, |acc, &bb| acc | bb)
+    };
+    let piece_bb = |color: Color, piece: Piece| pieces[color.to_index()][piece.to_index()];
+    let occupied = color_bb(Color::White) | color_bb(Color::Black);
+
+    for color in [Color::White, Color::Black] {
+        match piece_bb(color, Piece::King).popcnt() {
+            0 => return Err(FenError::MissingKing(color)),
+            1 => {}
+            _ => return Err(FenError::TooManyKings(color)),
+        }
+    }
+
+    let pawns = piece_bb(Color::White, Piece::Pawn) | piece_bb(Color::Black, Piece::Pawn);
+    for square in ALL_SQUARES {
+        let rank = square.get_rank();
+        if (rank == Rank::First || rank == Rank::Eighth)
+            && pawns & BitBoard::from_square(square) != EMPTY
+        {
+            return Err(FenError::PawnOnBackRank(square));
+        }
+    }
+
+    let inactive = !side;
+    let king = piece_bb(inactive, Piece::King).to_square();
+    let attackers = (get_knight_moves(king) & piece_bb(side, Piece::Knight))
+        | (get_king_moves(king) & piece_bb(side, Piece::King))
+        | (get_pawn_attacks(king, inactive, piece_bb(side, Piece::Pawn)))
+        | (get_bishop_moves(king, occupied)
+            & (piece_bb(side, Piece::Bishop) | piece_bb(side, Piece::Queen)))
+        | (get_rook_moves(king, occupied)
+            & (piece_bb(side, Piece::Rook) | piece_bb(side, Piece::Queen)));
+    if attackers != EMPTY {
+        return Err(FenError::InactiveSideInCheck(inactive));
+    }
+
+    for color in [Color::White, Color::Black] {
+        let right = rights[color.to_index()];
+        let rank = color.to_my_backrank();
+        let on = |piece: Piece, file: File| {
+            piece_bb(color, piece) & BitBoard::from_square(Square::make_square(rank, file)) != EMPTY
+        };
+        let king_home = on(Piece::King, File::E);
+        if (right.has_kingside() && !(king_home && on(Piece::Rook, File::H)))
+            || (right.has_queenside() && !(king_home && on(Piece::Rook, File::A)))
+        {
+            return Err(FenError::ImpossibleCastling(color));
+        }
+    }
+
+    if let Some(square) = en_passant {
+        let mover = !side;
+        let rank = match mover {
+            Color::White => Rank::Third,
+            Color::Black => Rank::Sixth,
+        };
+        let possible = square.get_rank() == rank
+            && occupied & BitBoard::from_square(square) == EMPTY
+            && square
+                .backward(mover)
+                .is_some_and(|origin| occupied & BitBoard::from_square(origin) == EMPTY)
+            && square.forward(mover).is_some_and(|pawn| {
+                piece_bb(mover, Piece::Pawn) & BitBoard::from_square(pawn) != EMPTY
+            });
+        if !possible {
+            return Err(FenError::ImpossibleEnPassant(square));
+        }
+    }
+
+    Ok(())
+}
@@ -1,5 +1,13 @@
 pub mod assets;
 pub mod chess;
+pub mod clock;
+pub mod engine;
+pub mod fen;
+pub mod notation;
 pub mod opiece;
+pub mod pgn;
+pub mod piece_set;
+pub mod setup;
 pub mod style;
+pub mod uci;
 pub mod widget;
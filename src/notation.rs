@@ -0,0 +1,312 @@
+use std::fmt;
+
+use chess::{ChessMove, File, MoveGen, Piece, Rank, Square};
+
+use crate::chess::{Annotation, GameState, Move};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum NotationError {
+    Empty,
+    Invalid(String),
+    Illegal(String),
+    Ambiguous(String),
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotationError::Empty => write!(f, "empty move"),
+            NotationError::Invalid(mv) => write!(f, "invalid move notation {mv:?}"),
+            NotationError::Illegal(mv) => write!(f, "illegal move {mv:?}"),
+            NotationError::Ambiguous(mv) => write!(f, "ambiguous move {mv:?}"),
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+impl Annotation {
+    pub fn san(&self) -> String {
+        let source = self.mv.get_source();
+        let dest = self.mv.get_dest();
+        let mut san = String::new();
+
+        match self.kind {
+            Move::Castling if dest.get_file().to_index() > source.get_file().to_index() => {
+                san.push_str("O-O")
+            }
+            Move::Castling => san.push_str("O-O-O"),
+            _ if self.piece == Piece::Pawn => {
+                if self.capture.is_some() {
+                    san.push(file_char(source.get_file()));
+                    san.push('x');
+                }
+                san.push_str(&dest.to_string());
+            }
+            _ => {
+                san.push(piece_char(self.piece));
+                if self.file_hint {
+                    san.push(file_char(source.get_file()));
+                }
+                if self.rank_hint {
+                    san.push(rank_char(source.get_rank()));
+                }
+                if self.capture.is_some() {
+                    san.push('x');
+                }
+                san.push_str(&dest.to_string());
+            }
+        }
+
+        self.push_suffix(&mut san);
+        san
+    }
+
+    pub fn lan(&self) -> String {
+        let source = self.mv.get_source();
+        let dest = self.mv.get_dest();
+        let mut lan = String::new();
+
+        match self.kind {
+            Move::Castling if dest.get_file().to_index() > source.get_file().to_index() => {
+                lan.push_str("O-O")
+            }
+            Move::Castling => lan.push_str("O-O-O"),
+            _ => {
+                if self.piece != Piece::Pawn {
+                    lan.push(piece_char(self.piece));
+                }
+                lan.push_str(&source.to_string());
+                lan.push(if self.capture.is_some() { 'x' } else { '-' });
+                lan.push_str(&dest.to_string());
+            }
+        }
+
+        self.push_suffix(&mut lan);
+        lan
+    }
+
+    pub fn uci(&self) -> String {
+        self.mv.to_string()
+    }
+
+    fn push_suffix(&self, notation: &mut String) {
+        if let Some(promotion) = self.mv.get_promotion() {
+            notation.push('=');
+            notation.push(piece_char(promotion));
+        }
+        if self.mate {
+            notation.push('#');
+        } else if self.check {
+            notation.push('+');
+        }
+    }
+}
+
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.san())
+    }
+}
+
+impl GameState {
+    pub fn san(&self, mv: ChessMove) -> String {
+        match self.make_move(mv).annotation {
+            Some(ant) => ant.san(),
+            None => mv.to_string(),
+        }
+    }
+
+    pub fn lan(&self, mv: ChessMove) -> String {
+        match self.make_move(mv).annotation {
+            Some(ant) => ant.lan(),
+            None => mv.to_string(),
+        }
+    }
+
+    pub fn parse_move(&self, text: &str) -> Result<ChessMove, NotationError> {
+        match self.parse_uci(text) {
+            Err(NotationError::Invalid(_)) => self.parse_san(text),
+            result => result,
+        }
+    }
+
+    pub fn parse_uci(&self, uci: &str) -> Result<ChessMove, NotationError> {
+        let invalid = || NotationError::Invalid(uci.to_string());
+        let chars: Vec<char> = uci.trim().chars().collect();
+
+        if chars.is_empty() {
+            return Err(NotationError::Empty);
+        }
+        if !(4..=5).contains(&chars.len()) {
+            return Err(invalid());
+        }
+
+        let square = |file: char, rank: char| -> Option<Square> {
+            Some(Square::make_square(char_rank(rank)?, char_file(file)?))
+        };
+        let source = square(chars[0], chars[1]).ok_or_else(invalid)?;
+        let dest = square(chars[2], chars[3]).ok_or_else(invalid)?;
+        let promotion = match chars.get(4) {
+            Some(&c) if c.is_ascii_lowercase() => Some(char_piece(c).ok_or_else(invalid)?),
+            Some(_) => return Err(invalid()),
+            None => None,
+        };
+
+        let mv = ChessMove::new(source, dest, promotion);
+        if self.board.legal(mv) {
+            Ok(mv)
+        } else {
+            Err(NotationError::Illegal(uci.to_string()))
+        }
+    }
+
+    pub fn parse_san(&self, san: &str) -> Result<ChessMove, NotationError> {
+        let board = self.board;
+        let text = san.trim();
+        let invalid = || NotationError::Invalid(text.to_string());
+        let illegal = || NotationError::Illegal(text.to_string());
+
+        let stripped = text.trim_end_matches(['+', '#', '!', '?']);
+        if stripped.is_empty() {
+            return Err(NotationError::Empty);
+        }
+
+        let castle = match stripped {
+            "O-O" | "0-0" => Some(File::G),
+            "O-O-O" | "0-0-0" => Some(File::C),
+            _ => None,
+        };
+        if let Some(file) = castle {
+            let king = board.king_square(board.side_to_move());
+            let dest = Square::make_square(king.get_rank(), file);
+            return MoveGen::new_legal(&board)
+                .find(|mv| mv.get_source() == king && mv.get_dest() == dest)
+                .ok_or_else(illegal);
+        }
+
+        let mut chars: Vec<char> = stripped.chars().collect();
+
+        let piece = match chars.first() {
+            Some(&c) if c.is_ascii_uppercase() => {
+                chars.remove(0);
+                char_piece(c).ok_or_else(invalid)?
+            }
+            _ => Piece::Pawn,
+        };
+
+        let promotion = match chars.last() {
+            Some(&c) if c.is_ascii_alphabetic() && char_file(c).is_none() => {
+                chars.pop();
+                if chars.last() == Some(&'=') {
+                    chars.pop();
+                }
+                Some(char_piece(c).ok_or_else(invalid)?)
+            }
+            _ => None,
+        };
+
+        let rank = chars.pop().and_then(char_rank).ok_or_else(invalid)?;
+        let file = chars.pop().and_then(char_file).ok_or_else(invalid)?;
+        let dest = Square::make_square(rank, file);
+
+        let mut from_file = None;
+        let mut from_rank = None;
+        for c in chars {
+            match c {
+                'x' | ':' | '-' => {}
+                _ if from_file.is_none() && from_rank.is_none() && char_file(c).is_some() => {
+                    from_file = char_file(c);
+                }
+                _ if from_rank.is_none() && char_rank(c).is_some() => from_rank = char_rank(c),
+                _ => return Err(invalid()),
+            }
+        }
+
+        let mut candidates = MoveGen::new_legal(&board).filter(|mv| {
+            let source = mv.get_source();
+            mv.get_dest() == dest
+                && mv.get_promotion() == promotion
+                && board.piece_on(source) == Some(piece)
+                && from_file.is_none_or(|f| source.get_file() == f)
+                && from_rank.is_none_or(|r| source.get_rank() == r)
+        });
+
+        let mv = candidates.next().ok_or_else(illegal)?;
+        match candidates.next() {
+            Some(_) => Err(NotationError::Ambiguous(text.to_string())),
+            None => Ok(mv),
+        }
+    }
+}
+
+pub fn piece_char(piece: Piece) -> char {
+    match piece {
+        Piece::Pawn => 'P',
+        Piece::Knight => 'N',
+        Piece::Bishop => 'B',
+        Piece::Rook => 'R',
+        Piece::Queen => 'Q',
+        Piece::King => 'K',
+    }
+}
+
+pub fn char_piece(c: char) -> Option<Piece> {
+    match c.to_ascii_uppercase() {
+        'P' => Some(Piece::Pawn),
+        'N' => Some(Piece::Knight),
+        'B' => Some(Piece::Bishop),
+        'R' => Some(Piece::Rook),
+        'Q' => Some(Piece::Queen),
+        'K' => Some(Piece::King),
+        _ => None,
+    }
+}
+
+pub(crate) fn file_char(file: File) -> char {
+    (b'a' + file.to_index() as u8) as char
+}
+
+pub(crate) fn rank_char(rank: Rank) -> char {
+    (b'1' + rank.to_index() as u8) as char
+}
+
+pub(crate) fn char_file(c: char) -> Option<File> {
+    ('a'..='h')
+        .contains(&c)
+        .then(|| File::from_index(c as usize - 'a' as usize))
+}
+
+pub(crate) fn char_rank(c: char) -> Option<Rank> {
+    ('1'..='8')
+        .contains(&c)
+        .then(|| Rank::from_index(c as usize - '1' as usize))
+}
+
+pub fn nag_glyph(nag: u8) -> Option<&'static str> {
+    Some(match nag {
+        1 => "!",
+        2 => "?",
+        3 => "!!",
+        4 => "??",
+        5 => "!?",
+        6 => "?!",
+        7 => "□",
+        10 => "=",
+        13 => "∞",
+        14 => "⩲",
+        15 => "⩱",
+        16 => "±",
+        17 => "∓",
+        18 => "+−",
+        19 => "−+",
+        22 | 23 => "⨀",
+        32 | 33 => "⟳",
+        36 | 37 => "→",
+        40 | 41 => "↑",
+        132 | 133 => "⇆",
+        138 | 139 => "⊕",
+        146 => "N",
+        _ => return None,
+    })
+}
@@ -129,9 +129,74 @@ const N8: Glyph = Glyph {
     size: Size::new(576.0, 768.0),
 };
 
+const N0: Glyph = Glyph {
+    path: "M 518 384 Q 518 543.1 448.9 655.5 Q 379.8 768 282 768 Q 184.2 768 115.1 655.5 Q 46 543.1 46 384 Q 46 224.9 115.1 112.5 Q 184.2 0 282 0 Q 379.8 0 448.9 112.5 Q 518 224.9 518 384 Z M 429 384 Q 429 258.9 385.9 170.5 Q 342.9 82 282 82 Q 221.1 82 178.1 170.5 Q 135 258.9 135 384 Q 135 509.1 178.1 597.5 Q 221.1 686 282 686 Q 342.9 686 385.9 597.5 Q 429 509.1 429 384 Z",
+    size: Size::new(576.0, 768.0),
+};
+const N9: Glyph = Glyph {
+    path: "M 278 0 Q 320 0 363 17 Q 406 34 441.5 74.5 Q 477 115 497.5 183.5 Q 518 252 518 355 Q 518 454 501 530.5 Q 484 607 451 660.5 Q 418 714 371.5 741 Q 325 768 267 768 Q 209 768 164 744 Q 119 720 89.5 678 Q 60 636 52 580 L 140 580 Q 151 625 182.5 655.5 Q 214 686 265 686 Q 316 686 353 653.5 Q 390 621 409.5 561 Q 429 501 429 416 L 423 416 Q 405 447 379.5 469 Q 354 491 323 503 Q 292 515 257 515 Q 200 515 151.5 482.5 Q 103 450 74.5 392 Q 46 334 46 260 Q 46 188 74.5 128.5 Q 103 69 155 34.5 Q 207 0 278 0 Z M 282 82 Q 241 82 207 106 Q 173 130 153.5 169.5 Q 134 209 134 260 Q 134 308 153 347.5 Q 172 387 205 410 Q 238 433 280 433 Q 311 433 338 419 Q 365 405 385.5 380.5 Q 406 356 417.5 324.5 Q 429 293 429 259 Q 429 212 410 171.5 Q 391 131 357.5 106.5 Q 324 82 282 82 Z",
+    size: Size::new(576.0, 768.0),
+};
+
+const PLUS: Glyph = Glyph {
+    path: "M 243 192 L 333 192 L 333 339 L 480 339 L 480 429 L 333 429 L 333 576 L 243 576 L 243 429 L 96 429 L 96 339 L 243 339 Z",
+    size: Size::new(576.0, 768.0),
+};
+const MINUS: Glyph = Glyph {
+    path: "M 96 339 L 480 339 L 480 429 L 96 429 Z",
+    size: Size::new(576.0, 768.0),
+};
+const DOT: Glyph = Glyph {
+    path: "M 90 660 L 198 660 L 198 768 L 90 768 Z",
+    size: Size::new(288.0, 768.0),
+};
+const HASH: Glyph = Glyph {
+    path: "M 178 96 L 258 96 L 258 672 L 178 672 Z M 318 96 L 398 96 L 398 672 L 318 672 Z M 46 250 L 530 250 L 530 330 L 46 330 Z M 46 438 L 530 438 L 530 518 L 46 518 Z",
+    size: Size::new(576.0, 768.0),
+};
+
 pub const ROW: &[Glyph] = &[N1, N2, N3, N4, N5, N6, N7, N8];
 pub const COL: &[Glyph] = &[NA, NB, NC, ND, NE, NF, NG, NH];
 
+pub fn glyph(c: char) -> Option<&'static Glyph> {
+    match c {
+        'a'..='h' => COL.get(c as usize - 'a' as usize),
+        '0' => Some(&N0),
+        '1'..='8' => ROW.get(c as usize - '1' as usize),
+        '9' => Some(&N9),
+        '+' => Some(&PLUS),
+        '-' => Some(&MINUS),
+        '.' => Some(&DOT),
+        '#' => Some(&HASH),
+        _ => None,
+    }
+}
+
+pub fn measure(text: &str, size: f32) -> Size {
+    let width = text
+        .chars()
+        .filter_map(glyph)
+        .map(|glyph| glyph.size.width)
+        .sum::<f32>();
+    Size::new(width * size / 768.0, size)
+}
+
+pub fn draw_text(frame: &mut canvas::Frame, text: &str, size: f32, offset: Point, color: Color) {
+    let mut x = offset.x;
+    for glyph in text.chars().filter_map(glyph) {
+        draw(
+            frame,
+            glyph,
+            size,
+            Point::new(x, offset.y),
+            color,
+            false,
+            false,
+        );
+        x += glyph.size.width * size / 768.0;
+    }
+}
+
 pub fn draw(
     frame: &mut canvas::Frame,
     glyph: &Glyph,
@@ -0,0 +1,834 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chess::Color;
+
+use crate::chess::{GameOutcome, GameState, GameStatus, GameTree, NodeId, Termination};
+use crate::fen::FenError;
+use crate::notation::NotationError;
+
+const SEVEN_TAG_ROSTER: [(&str, &str); 7] = [
+    ("Event", "?"),
+    ("Site", "?"),
+    ("Date", "????.??.??"),
+    ("Round", "?"),
+    ("White", "?"),
+    ("Black", "?"),
+    ("Result", "*"),
+];
+
+const LINE_WIDTH: usize = 80;
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum GameResult {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    #[default]
+    Ongoing,
+}
+
+impl GameResult {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameResult::WhiteWins => "1-0",
+            GameResult::BlackWins => "0-1",
+            GameResult::Draw => "1/2-1/2",
+            GameResult::Ongoing => "*",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "1-0" => Some(GameResult::WhiteWins),
+            "0-1" => Some(GameResult::BlackWins),
+            "1/2-1/2" => Some(GameResult::Draw),
+            "*" => Some(GameResult::Ongoing),
+            _ => None,
+        }
+    }
+}
+
+impl From<Option<GameOutcome>> for GameResult {
+    fn from(outcome: Option<GameOutcome>) -> Self {
+        match outcome.map(|outcome| outcome.winner) {
+            None => GameResult::Ongoing,
+            Some(None) => GameResult::Draw,
+            Some(Some(Color::White)) => GameResult::WhiteWins,
+            Some(Some(Color::Black)) => GameResult::BlackWins,
+        }
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PgnErrorKind {
+    UnexpectedChar(char),
+    UnexpectedToken(String),
+    UnexpectedEof,
+    UnterminatedString,
+    UnterminatedComment,
+    UnbalancedVariation,
+    InvalidNag(String),
+    InvalidFen(FenError),
+    InvalidMove(NotationError),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PgnError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: PgnErrorKind,
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: ", self.line, self.column)?;
+        match &self.kind {
+            PgnErrorKind::UnexpectedChar(c) => write!(f, "unexpected character {c:?}"),
+            PgnErrorKind::UnexpectedToken(t) => write!(f, "unexpected token {t:?}"),
+            PgnErrorKind::UnexpectedEof => write!(f, "unexpected end of input"),
+            PgnErrorKind::UnterminatedString => write!(f, "unterminated string"),
+            PgnErrorKind::UnterminatedComment => write!(f, "unterminated comment"),
+            PgnErrorKind::UnbalancedVariation => write!(f, "unbalanced variation"),
+            PgnErrorKind::InvalidNag(n) => write!(f, "invalid NAG {n:?}"),
+            PgnErrorKind::InvalidFen(err) => write!(f, "invalid FEN: {err}"),
+            PgnErrorKind::InvalidMove(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for PgnError {}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Game {
+    pub tags: Vec<(String, String)>,
+    pub tree: GameTree,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new(GameTree::default())
+    }
+}
+
+impl Game {
+    pub fn new(tree: GameTree) -> Self {
+        Self {
+            tags: Vec::new(),
+            tree,
+        }
+    }
+
+    pub fn result(&self) -> GameResult {
+        self.tree.outcome().into()
+    }
+
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn set_tag(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        let value = value.into();
+        match self.tags.iter_mut().find(|(key, _)| *key == name) {
+            Some((_, old)) => *old = value,
+            None => self.tags.push((name, value)),
+        }
+    }
+
+    pub fn remove_tag(&mut self, name: &str) -> Option<String> {
+        let index = self.tags.iter().position(|(key, _)| key == name)?;
+        Some(self.tags.remove(index).1)
+    }
+
+    pub fn to_pgn(&self) -> String {
+        let mut out = String::new();
+
+        for (name, default) in SEVEN_TAG_ROSTER {
+            let value = match name {
+                "Result" => self.result().as_str(),
+                _ => self.tag(name).unwrap_or(default),
+            };
+            write_tag(&mut out, name, value);
+        }
+        let root = self.tree.root();
+        if *root != GameState::default() && self.tag("FEN").is_none() {
+            write_tag(&mut out, "SetUp", "1");
+            write_tag(&mut out, "FEN", &root.to_fen());
+        }
+        if let Some(outcome) = self.tree.outcome()
+            && self.tag("Termination").is_none()
+        {
+            write_tag(&mut out, "Termination", termination_tag(&outcome));
+        }
+        for (name, value) in &self.tags {
+            if !SEVEN_TAG_ROSTER.iter().any(|(roster, _)| roster == name) {
+                write_tag(&mut out, name, value);
+            }
+        }
+        out.push('\n');
+
+        let mut writer = Writer::default();
+        for token in movetext(&self.tree) {
+            match token {
+                MovetextToken::Comment(comment) => writer.comment(comment),
+                MovetextToken::Number(number) => writer.token(&number),
+                MovetextToken::Move { san, nags, .. } => {
+                    writer.token(&san);
+                    for nag in nags {
+                        writer.token(&format!("${nag}"));
+                    }
+                }
+                MovetextToken::Open => writer.open(),
+                MovetextToken::Close => writer.close(),
+            }
+        }
+        writer.token(self.result().as_str());
+
+        out.push_str(&writer.finish());
+        out
+    }
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_pgn())
+    }
+}
+
+impl FromStr for Game {
+    type Err = PgnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        match parser.game()? {
+            Some(game) => Ok(game),
+            None => Err(parser.error(PgnErrorKind::UnexpectedEof)),
+        }
+    }
+}
+
+pub fn parse(s: &str) -> Result<Vec<Game>, PgnError> {
+    let mut parser = Parser::new(s);
+    let mut games = Vec::new();
+    while let Some(game) = parser.game()? {
+        games.push(game);
+    }
+    Ok(games)
+}
+
+pub fn write(games: &[Game]) -> String {
+    games
+        .iter()
+        .map(Game::to_pgn)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MovetextToken<'a> {
+    Comment(&'a str),
+    Number(String),
+    Move {
+        node: NodeId,
+        san: String,
+        nags: &'a [u8],
+        depth: usize,
+    },
+    Open,
+    Close,
+}
+
+pub fn movetext(tree: &GameTree) -> Vec<MovetextToken<'_>> {
+    let mut movetext = Movetext {
+        tree,
+        tokens: Vec::new(),
+    };
+
+    if let Some(root) = tree.node(GameTree::ROOT) {
+        movetext.comment(&root.comment);
+    }
+    movetext.line(GameTree::ROOT, 0, 0, true);
+
+    movetext.tokens
+}
+
+struct Movetext<'a> {
+    tree: &'a GameTree,
+    tokens: Vec<MovetextToken<'a>>,
+}
+
+impl<'a> Movetext<'a> {
+    fn line(&mut self, mut parent: NodeId, mut ply: usize, depth: usize, mut numbered: bool) {
+        let tree = self.tree;
+        while let Some(&main) = tree.children(parent).first() {
+            numbered = self.push_move(main, ply, depth, numbered);
+
+            for &variation in &tree.children(parent)[1..] {
+                self.tokens.push(MovetextToken::Open);
+                let inner = self.push_move(variation, ply, depth + 1, true);
+                self.line(variation, ply + 1, depth + 1, inner);
+                self.tokens.push(MovetextToken::Close);
+                numbered = true;
+            }
+
+            parent = main;
+            ply += 1;
+        }
+    }
+
+    fn push_move(&mut self, id: NodeId, ply: usize, depth: usize, numbered: bool) -> bool {
+        let tree = self.tree;
+        let node = tree.node(id).unwrap();
+        let Some(ant) = node.state.annotation else {
+            return numbered;
+        };
+
+        self.comment(&node.starting_comment);
+
+        let white = node.state.board.side_to_move() == Color::Black;
+        let number = self.move_number(ply);
+        if white {
+            self.tokens
+                .push(MovetextToken::Number(format!("{number}.")));
+        } else if numbered || !node.starting_comment.is_empty() {
+            self.tokens
+                .push(MovetextToken::Number(format!("{number}...")));
+        }
+
+        self.tokens.push(MovetextToken::Move {
+            node: id,
+            san: ant.san(),
+            nags: &node.nags,
+            depth,
+        });
+
+        self.comment(&node.comment);
+        !node.comment.is_empty()
+    }
+
+    fn comment(&mut self, comment: &'a str) {
+        if !comment.is_empty() {
+            self.tokens.push(MovetextToken::Comment(comment));
+        }
+    }
+
+    fn move_number(&self, ply: usize) -> usize {
+        let root = self.tree.root();
+        let offset = match root.board.side_to_move() {
+            Color::White => 0,
+            Color::Black => 1,
+        };
+        root.fullmove as usize + (ply + offset) / 2
+    }
+}
+
+pub fn split_commands(comment: &str) -> (String, Vec<(String, String)>) {
+    let mut text = String::new();
+    let mut commands = Vec::new();
+    let mut rest = comment;
+
+    while let Some(start) = rest.find("[%") {
+        let Some(len) = rest[start..].find(']') else {
+            break;
+        };
+        text.push_str(&rest[..start]);
+        text.push(' ');
+
+        let body = &rest[start + 2..start + len];
+        let (name, args) = body.split_once(' ').unwrap_or((body, ""));
+        commands.push((name.to_string(), args.trim().to_string()));
+        rest = &rest[start + len + 1..];
+    }
+    text.push_str(rest);
+
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (text, commands)
+}
+
+fn join_comment(comment: &str, text: &str) -> String {
+    if comment.is_empty() {
+        text.to_string()
+    } else {
+        format!("{comment} {text}")
+    }
+}
+
+fn termination_tag(outcome: &GameOutcome) -> &'static str {
+    match outcome.termination {
+        Termination::Timeout => "time forfeit",
+        Termination::Abandonment => "abandoned",
+        _ => "normal",
+    }
+}
+
+fn infer_outcome(
+    result: GameResult,
+    termination: Option<&str>,
+    last: &GameState,
+) -> Option<GameOutcome> {
+    let winner = match result {
+        GameResult::Ongoing => return None,
+        GameResult::WhiteWins => Some(Color::White),
+        GameResult::BlackWins => Some(Color::Black),
+        GameResult::Draw => None,
+    };
+
+    let termination = match (termination, last.status()) {
+        (Some("time forfeit"), _) => Termination::Timeout,
+        (Some("abandoned"), _) => Termination::Abandonment,
+        (_, GameStatus::Checkmate(_)) if winner.is_some() => Termination::Checkmate,
+        (_, GameStatus::Draw(reason)) if winner.is_none() => Termination::Draw(reason),
+        _ if winner.is_some() => Termination::Resignation,
+        _ => match last.claimable_draw() {
+            Some(reason) => Termination::Draw(reason),
+            None => Termination::DrawAgreement,
+        },
+    };
+
+    Some(GameOutcome {
+        winner,
+        termination,
+    })
+}
+
+fn write_tag(out: &mut String, name: &str, value: &str) {
+    let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+    out.push_str(&format!("[{name} \"{value}\"]\n"));
+}
+
+#[derive(Default)]
+struct Writer {
+    out: String,
+    line: usize,
+    open: bool,
+}
+
+impl Writer {
+    fn token(&mut self, token: &str) {
+        if self.line > 0 && !self.open {
+            if self.line + 1 + token.len() > LINE_WIDTH {
+                self.out.push('\n');
+                self.line = 0;
+            } else {
+                self.out.push(' ');
+                self.line += 1;
+            }
+        }
+        self.out.push_str(token);
+        self.line += token.len();
+        self.open = false;
+    }
+
+    fn comment(&mut self, comment: &str) {
+        self.token(&format!("{{{}}}", comment.replace('}', "")));
+    }
+
+    fn open(&mut self) {
+        self.token("(");
+        self.open = true;
+    }
+
+    fn close(&mut self) {
+        self.out.push(')');
+        self.line += 1;
+    }
+
+    fn finish(mut self) -> String {
+        self.out.push('\n');
+        self.out
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum Token {
+    TagOpen,
+    TagClose,
+    Str(String),
+    Symbol(String),
+    Comment(String),
+    Nag(u8),
+    Open,
+    Close,
+    Result(GameResult),
+}
+
+struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(s: &'a str) -> Self {
+        Self {
+            chars: s.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn error(&self, line: usize, column: usize, kind: PgnErrorKind) -> PgnError {
+        PgnError { line, column, kind }
+    }
+
+    fn skip_line(&mut self) -> String {
+        let mut text = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if c == '\n' {
+                break;
+            }
+            text.push(c);
+            self.bump();
+        }
+        text
+    }
+
+    fn token(&mut self) -> Result<Option<(Token, usize, usize)>, PgnError> {
+        loop {
+            let (line, column) = (self.line, self.column);
+            let Some(&c) = self.chars.peek() else {
+                return Ok(None);
+            };
+
+            let token = match c {
+                _ if c.is_whitespace() || c == '.' => {
+                    self.bump();
+                    continue;
+                }
+                '%' if column == 1 => {
+                    self.skip_line();
+                    continue;
+                }
+                '[' => {
+                    self.bump();
+                    Token::TagOpen
+                }
+                ']' => {
+                    self.bump();
+                    Token::TagClose
+                }
+                '(' => {
+                    self.bump();
+                    Token::Open
+                }
+                ')' => {
+                    self.bump();
+                    Token::Close
+                }
+                '*' => {
+                    self.bump();
+                    Token::Result(GameResult::Ongoing)
+                }
+                ';' => {
+                    self.bump();
+                    Token::Comment(self.skip_line().trim().to_string())
+                }
+                '{' => {
+                    self.bump();
+                    let mut text = String::new();
+                    loop {
+                        match self.bump() {
+                            Some('}') => break,
+                            Some(c) => text.push(c),
+                            None => {
+                                return Err(self.error(
+                                    line,
+                                    column,
+                                    PgnErrorKind::UnterminatedComment,
+                                ));
+                            }
+                        }
+                    }
+                    Token::Comment(text.trim().to_string())
+                }
+                '"' => {
+                    self.bump();
+                    let mut text = String::new();
+                    loop {
+                        match self.bump() {
+                            Some('"') => break,
+                            Some('\\') if self.chars.peek().is_some_and(|&c| c != '\n') => {
+                                text.extend(self.bump());
+                            }
+                            Some('\\' | '\n') | None => {
+                                return Err(self.error(
+                                    line,
+                                    column,
+                                    PgnErrorKind::UnterminatedString,
+                                ));
+                            }
+                            Some(c) => text.push(c),
+                        }
+                    }
+                    Token::Str(text)
+                }
+                '$' => {
+                    self.bump();
+                    let mut digits = String::new();
+                    while let Some(&c) = self.chars.peek() {
+                        if !c.is_ascii_digit() {
+                            break;
+                        }
+                        digits.push(c);
+                        self.bump();
+                    }
+                    let nag = digits
+                        .parse()
+                        .map_err(|_| self.error(line, column, PgnErrorKind::InvalidNag(digits)))?;
+                    Token::Nag(nag)
+                }
+                '!' | '?' => {
+                    let mut suffix = String::new();
+                    while let Some(&c) = self.chars.peek() {
+                        if c != '!' && c != '?' {
+                            break;
+                        }
+                        suffix.push(c);
+                        self.bump();
+                    }
+                    let nag = suffix_nag(&suffix).ok_or_else(|| {
+                        self.error(line, column, PgnErrorKind::InvalidNag(suffix.clone()))
+                    })?;
+                    Token::Nag(nag)
+                }
+                _ if c.is_ascii_alphanumeric() => {
+                    let mut symbol = String::new();
+                    while let Some(&c) = self.chars.peek() {
+                        if !(c.is_ascii_alphanumeric() || "_+#=:-/".contains(c)) {
+                            break;
+                        }
+                        symbol.push(c);
+                        self.bump();
+                    }
+                    match GameResult::parse(&symbol) {
+                        Some(result) => Token::Result(result),
+                        None if symbol.chars().all(|c| c.is_ascii_digit()) => continue,
+                        None => Token::Symbol(symbol),
+                    }
+                }
+                _ => {
+                    return Err(self.error(line, column, PgnErrorKind::UnexpectedChar(c)));
+                }
+            };
+
+            return Ok(Some((token, line, column)));
+        }
+    }
+}
+
+fn suffix_nag(suffix: &str) -> Option<u8> {
+    match suffix {
+        "!" => Some(1),
+        "?" => Some(2),
+        "!!" => Some(3),
+        "??" => Some(4),
+        "!?" => Some(5),
+        "?!" => Some(6),
+        _ => None,
+    }
+}
+
+struct Parser<'a> {
+    lexer: Lexer<'a>,
+    peeked: Option<(Token, usize, usize)>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Self {
+            lexer: Lexer::new(s),
+            peeked: None,
+        }
+    }
+
+    fn error(&self, kind: PgnErrorKind) -> PgnError {
+        match &self.peeked {
+            Some((_, line, column)) => PgnError {
+                line: *line,
+                column: *column,
+                kind,
+            },
+            None => PgnError {
+                line: self.lexer.line,
+                column: self.lexer.column,
+                kind,
+            },
+        }
+    }
+
+    fn peek(&mut self) -> Result<Option<&Token>, PgnError> {
+        if self.peeked.is_none() {
+            self.peeked = self.lexer.token()?;
+        }
+        Ok(self.peeked.as_ref().map(|(token, _, _)| token))
+    }
+
+    fn bump(&mut self) -> Result<Option<Token>, PgnError> {
+        self.peek()?;
+        Ok(self.peeked.take().map(|(token, _, _)| token))
+    }
+
+    fn unexpected(&self) -> PgnError {
+        match &self.peeked {
+            Some((token, _, _)) => self.error(PgnErrorKind::UnexpectedToken(format!("{token:?}"))),
+            None => self.error(PgnErrorKind::UnexpectedEof),
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), PgnError> {
+        if self.peek()? == Some(&expected) {
+            self.bump()?;
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn game(&mut self) -> Result<Option<Game>, PgnError> {
+        if self.peek()?.is_none() {
+            return Ok(None);
+        }
+
+        let mut game = Game::default();
+
+        while self.peek()? == Some(&Token::TagOpen) {
+            self.bump()?;
+            let name = match self.peek()? {
+                Some(Token::Symbol(name)) => name.clone(),
+                _ => return Err(self.unexpected()),
+            };
+            self.bump()?;
+            let value = match self.peek()? {
+                Some(Token::Str(value)) => value.clone(),
+                _ => return Err(self.unexpected()),
+            };
+            self.bump()?;
+            self.expect(Token::TagClose)?;
+            game.set_tag(name, value);
+        }
+
+        if let Some(fen) = game.tag("FEN") {
+            let root = GameState::from_fen(fen)
+                .map_err(|err| self.error(PgnErrorKind::InvalidFen(err)))?;
+            game.tree = GameTree::new(root);
+        }
+
+        let mut result = game
+            .tag("Result")
+            .and_then(GameResult::parse)
+            .unwrap_or_default();
+
+        self.line(&mut game, GameTree::ROOT, false)?;
+
+        if let Some(&Token::Result(token)) = self.peek()? {
+            self.bump()?;
+            result = token;
+        }
+        game.set_tag("Result", result.as_str());
+
+        let last = *game.tree.mainline().last().unwrap();
+        let outcome = infer_outcome(
+            result,
+            game.tag("Termination"),
+            game.tree.state(last).unwrap(),
+        );
+        game.tree.set_outcome(outcome);
+
+        Ok(Some(game))
+    }
+
+    fn line(&mut self, game: &mut Game, start: NodeId, nested: bool) -> Result<(), PgnError> {
+        let mut parent = start;
+        let mut last: Option<NodeId> = None;
+        let mut pending = String::new();
+
+        loop {
+            match self.peek()? {
+                Some(Token::Symbol(san)) => {
+                    let san = san.clone();
+                    let state = game.tree.state(parent).unwrap();
+                    let mv = state
+                        .parse_san(&san)
+                        .map_err(|err| self.error(PgnErrorKind::InvalidMove(err)))?;
+                    self.bump()?;
+
+                    let id = game.tree.add_move(parent, mv).unwrap();
+                    if !pending.is_empty() {
+                        game.tree
+                            .set_starting_comment(id, std::mem::take(&mut pending));
+                    }
+                    last = Some(id);
+                    parent = id;
+                }
+                Some(Token::Comment(text)) => {
+                    let text = text.clone();
+                    self.bump()?;
+                    let node = match last {
+                        Some(id) => Some(id),
+                        None if !nested => Some(GameTree::ROOT),
+                        None => None,
+                    };
+                    match node {
+                        Some(id) => {
+                            let comment = &game.tree.node(id).unwrap().comment;
+                            let comment = join_comment(comment, &text);
+                            game.tree.set_comment(id, comment);
+                        }
+                        None => pending = join_comment(&pending, &text),
+                    }
+                }
+                Some(Token::Nag(nag)) => {
+                    let nag = *nag;
+                    let Some(id) = last else {
+                        return Err(self.unexpected());
+                    };
+                    self.bump()?;
+                    let mut nags = game.tree.node(id).unwrap().nags.clone();
+                    nags.push(nag);
+                    game.tree.set_nags(id, nags);
+                }
+                Some(Token::Open) => {
+                    let Some(id) = last else {
+                        return Err(self.unexpected());
+                    };
+                    self.bump()?;
+                    let branch = game.tree.parent(id).unwrap();
+                    self.line(game, branch, true)?;
+                }
+                Some(Token::Close) if nested => {
+                    self.bump()?;
+                    return Ok(());
+                }
+                Some(Token::Close) => {
+                    return Err(self.error(PgnErrorKind::UnbalancedVariation));
+                }
+                Some(Token::Result(_) | Token::TagOpen) | None if nested => {
+                    return Err(self.error(PgnErrorKind::UnbalancedVariation));
+                }
+                Some(Token::Result(_) | Token::TagOpen) | None => return Ok(()),
+                Some(_) => return Err(self.unexpected()),
+            }
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+
+use chess::{ALL_COLORS, ALL_PIECES, Color, Piece};
+use iced::advanced::image;
+use resvg::{tiny_skia, usvg};
+
+use crate::{assets::Assets, notation::piece_char};
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+const GZIP_SIGNATURE: &[u8] = b"\x1f\x8b";
+const MAX_RASTER: u32 = 2048;
+const MAX_CACHED: usize = 12 * 8;
+
+static EMBEDDED: LazyLock<PieceSet> = LazyLock::new(|| {
+    let files = Assets::iter().filter_map(|path| {
+        let name = path.strip_prefix("pieces/")?.to_string();
+        Some((name, Assets::get(&path)?.data.into_owned()))
+    });
+    PieceSet::from_files(files).expect("embedded piece set is valid")
+});
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PieceSetError {
+    Missing(Piece, Color),
+    Io(PathBuf, String),
+    UnknownFormat(Piece, Color),
+    InvalidPng(Piece, Color, String),
+    InvalidSvg(Piece, Color, String),
+}
+
+impl fmt::Display for PieceSetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PieceSetError::Missing(piece, color) => {
+                write!(f, "no image for {color:?} {piece:?}")
+            }
+            PieceSetError::Io(path, error) => write!(f, "cannot read {}: {error}", path.display()),
+            PieceSetError::UnknownFormat(piece, color) => {
+                write!(f, "{color:?} {piece:?} is neither PNG nor SVG")
+            }
+            PieceSetError::InvalidPng(piece, color, error) => {
+                write!(f, "invalid PNG for {color:?} {piece:?}: {error}")
+            }
+            PieceSetError::InvalidSvg(piece, color, error) => {
+                write!(f, "invalid SVG for {color:?} {piece:?}: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PieceSetError {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    Png,
+    Svg,
+}
+
+impl Format {
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        if data.starts_with(PNG_SIGNATURE) {
+            return Some(Format::Png);
+        }
+        if data.starts_with(GZIP_SIGNATURE) {
+            return Some(Format::Svg);
+        }
+        let text = data.strip_prefix(b"\xef\xbb\xbf").unwrap_or(data);
+        let start = text.iter().position(|b| !b.is_ascii_whitespace())?;
+        (text[start] == b'<').then_some(Format::Svg)
+    }
+}
+
+enum Source {
+    Raster(image::Handle),
+    Vector(usvg::Tree),
+}
+
+struct Inner {
+    sources: [[Source; 6]; 2],
+    rasters: Mutex<HashMap<(u32, usize, usize), image::Handle>>,
+}
+
+#[derive(Clone)]
+pub struct PieceSet(Arc<Inner>);
+
+impl Default for PieceSet {
+    fn default() -> Self {
+        EMBEDDED.clone()
+    }
+}
+
+impl PartialEq for PieceSet {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl fmt::Debug for PieceSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PieceSet")
+            .field("vector", &self.is_vector())
+            .finish_non_exhaustive()
+    }
+}
+
+impl PieceSet {
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self, PieceSetError> {
+        let dir = dir.as_ref();
+        fs::read_dir(dir).map_err(|error| PieceSetError::Io(dir.into(), error.to_string()))?;
+        Self::load(|name| {
+            let path = dir.join(name);
+            match fs::read(&path) {
+                Ok(data) => Ok(Some(data)),
+                Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+                Err(error) => Err(PieceSetError::Io(path, error.to_string())),
+            }
+        })
+    }
+
+    pub fn from_files<N, D>(files: impl IntoIterator<Item = (N, D)>) -> Result<Self, PieceSetError>
+    where
+        N: Into<String>,
+        D: Into<Vec<u8>>,
+    {
+        let mut files: HashMap<String, Vec<u8>> = files
+            .into_iter()
+            .map(|(name, data)| (name.into(), data.into()))
+            .collect();
+        Self::load(|name| Ok(files.remove(name)))
+    }
+
+    fn load(
+        mut read: impl FnMut(&str) -> Result<Option<Vec<u8>>, PieceSetError>,
+    ) -> Result<Self, PieceSetError> {
+        let mut load = |piece: Piece, color: Color| -> Result<Source, PieceSetError> {
+            for name in file_names(piece, color) {
+                if let Some(data) = read(&name)? {
+                    return decode(&data, piece, color);
+                }
+            }
+            Err(PieceSetError::Missing(piece, color))
+        };
+
+        let mut sources = Vec::with_capacity(12);
+        for color in ALL_COLORS {
+            for piece in ALL_PIECES {
+                sources.push(load(piece, color)?);
+            }
+        }
+        let mut sources = sources.into_iter();
+        let sources = [(); 2].map(|_| [(); 6].map(|_| sources.next().unwrap()));
+
+        Ok(Self(Arc::new(Inner {
+            sources,
+            rasters: Mutex::new(HashMap::new()),
+        })))
+    }
+
+    pub fn is_vector(&self) -> bool {
+        self.0
+            .sources
+            .iter()
+            .flatten()
+            .any(|source| matches!(source, Source::Vector(_)))
+    }
+
+    pub fn get(&self, piece: Piece, color: Color, size: f32) -> image::Handle {
+        let tree = match &self.0.sources[color.to_index()][piece.to_index()] {
+            Source::Raster(handle) => return handle.clone(),
+            Source::Vector(tree) => tree,
+        };
+
+        let px = (size.ceil() as u32).clamp(1, MAX_RASTER);
+        let key = (px, color.to_index(), piece.to_index());
+        let mut rasters = self.0.rasters.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(handle) = rasters.get(&key) {
+            return handle.clone();
+        }
+        if rasters.len() >= MAX_CACHED {
+            rasters.clear();
+        }
+        let handle = rasterize(tree, px);
+        rasters.insert(key, handle.clone());
+        handle
+    }
+}
+
+fn file_names(piece: Piece, color: Color) -> Vec<String> {
+    let side = match color {
+        Color::White => 'w',
+        Color::Black => 'b',
+    };
+    let upper = piece_char(piece);
+    let lower = upper.to_ascii_lowercase();
+
+    ["svg", "svgz", "png"]
+        .iter()
+        .flat_map(|ext| {
+            [
+                format!("{side}{lower}.{ext}"),
+                format!("{side}{upper}.{ext}"),
+            ]
+        })
+        .collect()
+}
+
+fn decode(data: &[u8], piece: Piece, color: Color) -> Result<Source, PieceSetError> {
+    match Format::detect(data) {
+        Some(Format::Png) => {
+            let pixmap = tiny_skia::Pixmap::decode_png(data)
+                .map_err(|e| PieceSetError::InvalidPng(piece, color, e.to_string()))?;
+            Ok(Source::Raster(to_handle(pixmap)))
+        }
+        Some(Format::Svg) => usvg::Tree::from_data(data, &usvg::Options::default())
+            .map(Source::Vector)
+            .map_err(|e| PieceSetError::InvalidSvg(piece, color, e.to_string())),
+        None => Err(PieceSetError::UnknownFormat(piece, color)),
+    }
+}
+
+fn rasterize(tree: &usvg::Tree, px: u32) -> image::Handle {
+    let mut pixmap = tiny_skia::Pixmap::new(px, px).expect("raster size is clamped");
+    let size = tree.size();
+    let scale = px as f32 / size.width().max(size.height());
+    let transform = tiny_skia::Transform::from_scale(scale, scale).post_translate(
+        (px as f32 - size.width() * scale) / 2.0,
+        (px as f32 - size.height() * scale) / 2.0,
+    );
+    resvg::render(tree, transform, &mut pixmap.as_mut());
+    to_handle(pixmap)
+}
+
+fn to_handle(pixmap: tiny_skia::Pixmap) -> image::Handle {
+    let rgba: Vec<u8> = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    image::Handle::from_rgba(pixmap.width(), pixmap.height(), rgba)
+}
@@ -0,0 +1,171 @@
+use chess::{ALL_SQUARES, CastleRights, Color, File, Piece, Rank, Square};
+
+use crate::{
+    chess::GameState,
+    fen::{FenError, placement},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Setup {
+    pub pieces: [Option<(Piece, Color)>; 64],
+    pub side: Color,
+    pub castling: [CastleRights; 2],
+    pub en_passant: Option<Square>,
+}
+
+impl Default for Setup {
+    fn default() -> Self {
+        Self::from_state(&GameState::default())
+    }
+}
+
+impl Setup {
+    pub fn empty() -> Self {
+        Self {
+            pieces: [None; 64],
+            side: Color::White,
+            castling: [CastleRights::NoRights; 2],
+            en_passant: None,
+        }
+    }
+
+    pub fn from_state(game: &GameState) -> Self {
+        let board = &game.board;
+        let mut pieces = [None; 64];
+        for square in ALL_SQUARES {
+            pieces[square.to_index()] = board.piece_on(square).zip(board.color_on(square));
+        }
+        let side = board.side_to_move();
+
+        Self {
+            pieces,
+            side,
+            castling: [
+                board.castle_rights(Color::White),
+                board.castle_rights(Color::Black),
+            ],
+            en_passant: board.en_passant().and_then(|sq| sq.forward(side)),
+        }
+    }
+
+    pub fn piece(&self, square: Square) -> Option<(Piece, Color)> {
+        self.pieces[square.to_index()]
+    }
+
+    pub fn set(&mut self, square: Square, piece: Option<(Piece, Color)>) {
+        self.pieces[square.to_index()] = piece;
+    }
+
+    pub fn relocate(&mut self, from: Square, to: Square) {
+        if from != to {
+            let piece = self.piece(from);
+            self.set(from, None);
+            self.set(to, piece);
+        }
+    }
+
+    pub fn toggle_castling(&mut self, color: Color, right: CastleRights) {
+        let rights = &mut self.castling[color.to_index()];
+        let held = match right {
+            CastleRights::KingSide => rights.has_kingside(),
+            CastleRights::QueenSide => rights.has_queenside(),
+            _ => false,
+        };
+        *rights = if held {
+            rights.remove(right)
+        } else {
+            rights.add(right)
+        };
+    }
+
+    pub fn mirror(&self) -> Self {
+        let mut mirrored = Self {
+            castling: [CastleRights::NoRights; 2],
+            en_passant: self
+                .en_passant
+                .map(|sq| Square::make_square(sq.get_rank(), mirror_file(sq.get_file()))),
+            ..*self
+        };
+        for square in ALL_SQUARES {
+            let target = Square::make_square(square.get_rank(), mirror_file(square.get_file()));
+            mirrored.set(target, self.piece(square));
+        }
+        mirrored
+    }
+
+    pub fn flip_colors(&self) -> Self {
+        let mut flipped = Self {
+            side: !self.side,
+            castling: [self.castling[1], self.castling[0]],
+            en_passant: self
+                .en_passant
+                .map(|sq| Square::make_square(mirror_rank(sq.get_rank()), sq.get_file())),
+            ..*self
+        };
+        for square in ALL_SQUARES {
+            let target = Square::make_square(mirror_rank(square.get_rank()), square.get_file());
+            flipped.set(
+                target,
+                self.piece(square).map(|(piece, color)| (piece, !color)),
+            );
+        }
+        flipped
+    }
+
+    pub fn en_passant_squares(&self) -> Vec<Square> {
+        let mover = !self.side;
+        let rank = match mover {
+            Color::White => Rank::Third,
+            Color::Black => Rank::Sixth,
+        };
+
+        (0..8)
+            .map(|file| Square::make_square(rank, File::from_index(file)))
+            .filter(|&square| {
+                self.piece(square).is_none()
+                    && square
+                        .backward(mover)
+                        .is_some_and(|origin| self.piece(origin).is_none())
+                    && square
+                        .forward(mover)
+                        .is_some_and(|pawn| self.piece(pawn) == Some((Piece::Pawn, mover)))
+            })
+            .collect()
+    }
+
+    pub fn to_fen(&self) -> String {
+        let mut fen = placement(&self.pieces);
+
+        fen.push_str(match self.side {
+            Color::White => " w ",
+            Color::Black => " b ",
+        });
+
+        let castling = format!(
+            "{}{}",
+            self.castling[Color::White.to_index()].to_string(Color::White),
+            self.castling[Color::Black.to_index()].to_string(Color::Black)
+        );
+        fen.push_str(if castling.is_empty() { "-" } else { &castling });
+
+        match self.en_passant {
+            Some(square) => fen.push_str(&format!(" {square}")),
+            None => fen.push_str(" -"),
+        }
+
+        fen.push_str(" 0 1");
+        fen
+    }
+
+    pub fn game(&self) -> Result<GameState, FenError> {
+        GameState::from_fen(&self.to_fen())
+    }
+}
+
+fn mirror_file(file: File) -> File {
+    File::from_index(7 - file.to_index())
+}
+
+fn mirror_rank(rank: Rank) -> Rank {
+    Rank::from_index(7 - rank.to_index())
+}
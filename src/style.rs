@@ -1,2 +1,7 @@
+pub mod captured_pieces;
 pub mod chess_board;
+pub mod chess_clock;
+pub mod eval_bar;
 pub mod game_buttons;
+pub mod notation_panel;
+pub mod setup_panel;
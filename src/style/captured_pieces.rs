@@ -0,0 +1,36 @@
+use iced::{Color, Theme};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Style {
+    pub background: Color,
+    pub font: Color,
+}
+
+pub trait Catalog {
+    type Class<'a>;
+
+    fn default<'a>() -> Self::Class<'a>;
+
+    fn style(&self, class: &Self::Class<'_>) -> Style;
+}
+
+pub type StyleFn<'a, Theme> = Box<dyn Fn(&Theme) -> Style + 'a>;
+
+impl Catalog for Theme {
+    type Class<'a> = StyleFn<'a, Self>;
+
+    fn default<'a>() -> Self::Class<'a> {
+        Box::new(default)
+    }
+
+    fn style(&self, class: &Self::Class<'_>) -> Style {
+        class(self)
+    }
+}
+
+pub fn default(_theme: &Theme) -> Style {
+    Style {
+        background: Color::TRANSPARENT,
+        font: Color::from_rgb8(140, 139, 138),
+    }
+}
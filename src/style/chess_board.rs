@@ -6,14 +6,28 @@ pub struct BoardStyle {
     pub dark: Color,
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Brushes {
+    pub green: Color,
+    pub red: Color,
+    pub blue: Color,
+    pub yellow: Color,
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct OverlayStyle {
     pub selected: Color,
     pub prev_move: Color,
     pub drag: Color,
     pub hover: Color,
-    pub highlight: Color,
-    pub arrow: Color,
+    pub brushes: Brushes,
+    pub premove: Color,
+    pub promotion: Color,
+    pub promotion_backdrop: Color,
+    pub input: Color,
+    pub input_font: Color,
+    pub input_error: Color,
+    pub focus: Color,
 }
 
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
@@ -55,8 +69,19 @@ pub fn default(_theme: &Theme) -> Style {
             selected: Color::from_rgba8(255, 255, 51, 0.5),
             drag: Color::from_rgba8(0, 0, 0, 0.14),
             hover: Color::from_rgba8(255, 255, 255, 0.65),
-            highlight: Color::from_rgba8(235, 97, 80, 0.8),
-            arrow: Color::from_rgba8(255, 170, 0, 0.64),
+            brushes: Brushes {
+                green: Color::from_rgba8(21, 120, 27, 0.8),
+                red: Color::from_rgba8(136, 32, 32, 0.8),
+                blue: Color::from_rgba8(0, 48, 136, 0.8),
+                yellow: Color::from_rgba8(230, 143, 0, 0.8),
+            },
+            premove: Color::from_rgba8(20, 85, 30, 0.5),
+            promotion: Color::from_rgb8(176, 176, 176),
+            promotion_backdrop: Color::from_rgba8(0, 0, 0, 0.45),
+            input: Color::from_rgba8(38, 36, 33, 0.9),
+            input_font: Color::from_rgb8(255, 255, 255),
+            input_error: Color::from_rgb8(235, 97, 80),
+            focus: Color::from_rgb8(30, 144, 255),
         },
     }
 }
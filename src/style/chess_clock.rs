@@ -0,0 +1,62 @@
+use iced::{Color, Theme};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Face {
+    pub background: Color,
+    pub font: Color,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Style {
+    pub idle: Face,
+    pub running: Face,
+    pub low_time: Face,
+    pub flagged: Face,
+    pub indicator: Color,
+    pub radius: f32,
+}
+
+pub trait Catalog {
+    type Class<'a>;
+
+    fn default<'a>() -> Self::Class<'a>;
+
+    fn style(&self, class: &Self::Class<'_>) -> Style;
+}
+
+pub type StyleFn<'a, Theme> = Box<dyn Fn(&Theme) -> Style + 'a>;
+
+impl Catalog for Theme {
+    type Class<'a> = StyleFn<'a, Self>;
+
+    fn default<'a>() -> Self::Class<'a> {
+        Box::new(default)
+    }
+
+    fn style(&self, class: &Self::Class<'_>) -> Style {
+        class(self)
+    }
+}
+
+pub fn default(_theme: &Theme) -> Style {
+    Style {
+        idle: Face {
+            background: Color::from_rgb8(54, 52, 52),
+            font: Color::from_rgb8(140, 139, 138),
+        },
+        running: Face {
+            background: Color::from_rgb8(235, 236, 208),
+            font: Color::from_rgb8(38, 36, 33),
+        },
+        low_time: Face {
+            background: Color::from_rgb8(133, 45, 35),
+            font: Color::from_rgb8(255, 255, 255),
+        },
+        flagged: Face {
+            background: Color::from_rgb8(202, 52, 49),
+            font: Color::from_rgb8(255, 255, 255),
+        },
+        indicator: Color::from_rgb8(115, 149, 82),
+        radius: 4.0,
+    }
+}
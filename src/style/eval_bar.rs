@@ -0,0 +1,44 @@
+use iced::{Color, Theme};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Style {
+    pub white: Color,
+    pub black: Color,
+    pub white_font: Color,
+    pub black_font: Color,
+    pub midline: Color,
+    pub radius: f32,
+}
+
+pub trait Catalog {
+    type Class<'a>;
+
+    fn default<'a>() -> Self::Class<'a>;
+
+    fn style(&self, class: &Self::Class<'_>) -> Style;
+}
+
+pub type StyleFn<'a, Theme> = Box<dyn Fn(&Theme) -> Style + 'a>;
+
+impl Catalog for Theme {
+    type Class<'a> = StyleFn<'a, Self>;
+
+    fn default<'a>() -> Self::Class<'a> {
+        Box::new(default)
+    }
+
+    fn style(&self, class: &Self::Class<'_>) -> Style {
+        class(self)
+    }
+}
+
+pub fn default(_theme: &Theme) -> Style {
+    Style {
+        white: Color::from_rgb8(240, 240, 240),
+        black: Color::from_rgb8(64, 61, 57),
+        white_font: Color::from_rgb8(64, 61, 57),
+        black_font: Color::from_rgb8(240, 240, 240),
+        midline: Color::from_rgba8(214, 79, 0, 0.6),
+        radius: 2.0,
+    }
+}
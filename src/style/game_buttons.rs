@@ -9,6 +9,9 @@ pub struct Button {
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct Style {
     pub button: Button,
+    pub hovered: Button,
+    pub disabled: Button,
+    pub radius: f32,
 }
 
 pub trait Catalog {
@@ -39,5 +42,14 @@ pub fn default(_theme: &Theme) -> Style {
             background: Color::from_rgb8(54, 52, 52),
             font: Color::from_rgb8(198, 197, 197),
         },
+        hovered: Button {
+            background: Color::from_rgb8(74, 72, 70),
+            font: Color::from_rgb8(255, 255, 255),
+        },
+        disabled: Button {
+            background: Color::from_rgb8(44, 42, 41),
+            font: Color::from_rgb8(92, 91, 90),
+        },
+        radius: 4.0,
     }
 }
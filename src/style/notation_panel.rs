@@ -0,0 +1,50 @@
+use iced::{Color, Theme};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Style {
+    pub background: Color,
+    pub number: Color,
+    pub mainline: Color,
+    pub variation: Color,
+    pub comment: Color,
+    pub hovered: Color,
+    pub current: Color,
+    pub current_font: Color,
+    pub radius: f32,
+}
+
+pub trait Catalog {
+    type Class<'a>;
+
+    fn default<'a>() -> Self::Class<'a>;
+
+    fn style(&self, class: &Self::Class<'_>) -> Style;
+}
+
+pub type StyleFn<'a, Theme> = Box<dyn Fn(&Theme) -> Style + 'a>;
+
+impl Catalog for Theme {
+    type Class<'a> = StyleFn<'a, Self>;
+
+    fn default<'a>() -> Self::Class<'a> {
+        Box::new(default)
+    }
+
+    fn style(&self, class: &Self::Class<'_>) -> Style {
+        class(self)
+    }
+}
+
+pub fn default(_theme: &Theme) -> Style {
+    Style {
+        background: Color::from_rgb8(38, 36, 33),
+        number: Color::from_rgb8(140, 139, 138),
+        mainline: Color::from_rgb8(198, 197, 197),
+        variation: Color::from_rgb8(128, 164, 196),
+        comment: Color::from_rgb8(115, 149, 82),
+        hovered: Color::from_rgb8(64, 61, 57),
+        current: Color::from_rgb8(80, 110, 140),
+        current_font: Color::from_rgb8(255, 255, 255),
+        radius: 3.0,
+    }
+}
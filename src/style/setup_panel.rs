@@ -0,0 +1,64 @@
+use iced::{Color, Theme};
+
+use super::game_buttons::Button;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Style {
+    pub background: Color,
+    pub label: Color,
+    pub button: Button,
+    pub hovered: Button,
+    pub active: Button,
+    pub disabled: Button,
+    pub valid: Color,
+    pub invalid: Color,
+    pub radius: f32,
+}
+
+pub trait Catalog {
+    type Class<'a>;
+
+    fn default<'a>() -> Self::Class<'a>;
+
+    fn style(&self, class: &Self::Class<'_>) -> Style;
+}
+
+pub type StyleFn<'a, Theme> = Box<dyn Fn(&Theme) -> Style + 'a>;
+
+impl Catalog for Theme {
+    type Class<'a> = StyleFn<'a, Self>;
+
+    fn default<'a>() -> Self::Class<'a> {
+        Box::new(default)
+    }
+
+    fn style(&self, class: &Self::Class<'_>) -> Style {
+        class(self)
+    }
+}
+
+pub fn default(_theme: &Theme) -> Style {
+    Style {
+        background: Color::from_rgb8(38, 36, 33),
+        label: Color::from_rgb8(150, 149, 148),
+        button: Button {
+            background: Color::from_rgb8(54, 52, 52),
+            font: Color::from_rgb8(198, 197, 197),
+        },
+        hovered: Button {
+            background: Color::from_rgb8(74, 72, 70),
+            font: Color::from_rgb8(255, 255, 255),
+        },
+        active: Button {
+            background: Color::from_rgb8(98, 153, 36),
+            font: Color::from_rgb8(255, 255, 255),
+        },
+        disabled: Button {
+            background: Color::from_rgb8(44, 42, 41),
+            font: Color::from_rgb8(92, 91, 90),
+        },
+        valid: Color::from_rgb8(129, 182, 76),
+        invalid: Color::from_rgb8(235, 97, 80),
+        radius: 4.0,
+    }
+}
@@ -0,0 +1,627 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+
+use chess::{ChessMove, Color, Square};
+use futures::{SinkExt, Stream, StreamExt, channel::mpsc};
+use iced::{Subscription, Task};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command as Process};
+
+use crate::chess::GameState;
+use crate::clock::{Clock, Increment};
+use crate::notation::{char_file, char_piece, char_rank};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum UciError {
+    Io(String),
+    Disconnected,
+}
+
+impl fmt::Display for UciError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UciError::Io(error) => write!(f, "engine io error: {error}"),
+            UciError::Disconnected => write!(f, "engine disconnected"),
+        }
+    }
+}
+
+impl std::error::Error for UciError {}
+
+impl From<io::Error> for UciError {
+    fn from(error: io::Error) -> Self {
+        UciError::Io(error.to_string())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Score {
+    Cp(i32),
+    Mate(i32),
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Bound {
+    #[default]
+    Exact,
+    Lower,
+    Upper,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Info {
+    pub depth: Option<u32>,
+    pub seldepth: Option<u32>,
+    pub multipv: Option<u32>,
+    pub score: Option<Score>,
+    pub bound: Bound,
+    pub nodes: Option<u64>,
+    pub nps: Option<u64>,
+    pub time: Option<Duration>,
+    pub hashfull: Option<u32>,
+    pub currmove: Option<ChessMove>,
+    pub pv: Vec<ChessMove>,
+    pub string: Option<String>,
+}
+
+impl Info {
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut tokens = line.split_whitespace();
+        if tokens.next()? != "info" {
+            return None;
+        }
+
+        let mut info = Info::default();
+        while let Some(token) = tokens.next() {
+            match token {
+                "depth" => info.depth = tokens.next()?.parse().ok(),
+                "seldepth" => info.seldepth = tokens.next()?.parse().ok(),
+                "multipv" => info.multipv = tokens.next()?.parse().ok(),
+                "nodes" => info.nodes = tokens.next()?.parse().ok(),
+                "nps" => info.nps = tokens.next()?.parse().ok(),
+                "hashfull" => info.hashfull = tokens.next()?.parse().ok(),
+                "time" => info.time = tokens.next()?.parse().ok().map(Duration::from_millis),
+                "currmove" => info.currmove = parse_move(tokens.next()?),
+                "score" => {
+                    let kind = tokens.next()?;
+                    let value = tokens.next()?.parse().ok()?;
+                    info.score = match kind {
+                        "cp" => Some(Score::Cp(value)),
+                        "mate" => Some(Score::Mate(value)),
+                        _ => return None,
+                    };
+                }
+                "lowerbound" => info.bound = Bound::Lower,
+                "upperbound" => info.bound = Bound::Upper,
+                "pv" => {
+                    info.pv = tokens.by_ref().map_while(parse_move).collect();
+                }
+                "string" => {
+                    info.string = Some(tokens.by_ref().collect::<Vec<_>>().join(" "));
+                }
+                _ => {}
+            }
+        }
+
+        Some(info)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BestMove {
+    pub mv: Option<ChessMove>,
+    pub ponder: Option<ChessMove>,
+}
+
+impl BestMove {
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut tokens = line.split_whitespace();
+        if tokens.next()? != "bestmove" {
+            return None;
+        }
+
+        let mv = parse_move(tokens.next()?);
+        let ponder = match (tokens.next(), tokens.next()) {
+            (Some("ponder"), Some(ponder)) => parse_move(ponder),
+            _ => None,
+        };
+
+        Some(BestMove { mv, ponder })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum OptionKind {
+    Check { default: bool },
+    Spin { default: i64, min: i64, max: i64 },
+    Combo { default: String, vars: Vec<String> },
+    Button,
+    String { default: String },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct EngineOption {
+    pub name: String,
+    pub kind: OptionKind,
+}
+
+impl EngineOption {
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.strip_prefix("option name ")?;
+        let (name, rest) = line.split_once(" type ")?;
+        let mut tokens = rest.split_whitespace();
+        let kind = tokens.next()?;
+
+        let mut default = None;
+        let mut min = None;
+        let mut max = None;
+        let mut vars = Vec::new();
+        let mut key = None;
+        let mut value = Vec::new();
+
+        let mut flush = |key: Option<&str>, value: &mut Vec<&str>| {
+            let text = value.join(" ");
+            value.clear();
+            match key {
+                Some("default") => default = Some(text),
+                Some("min") => min = text.parse().ok(),
+                Some("max") => max = text.parse().ok(),
+                Some("var") => vars.push(text),
+                _ => {}
+            }
+        };
+
+        for token in tokens {
+            match token {
+                "default" | "min" | "max" | "var" => {
+                    flush(key, &mut value);
+                    key = Some(token);
+                }
+                _ => value.push(token),
+            }
+        }
+        flush(key, &mut value);
+
+        let default = default.filter(|value| value != "<empty>");
+        let kind = match kind {
+            "check" => OptionKind::Check {
+                default: default.as_deref() == Some("true"),
+            },
+            "spin" => OptionKind::Spin {
+                default: default.and_then(|value| value.parse().ok()).unwrap_or(0),
+                min: min.unwrap_or(i64::MIN),
+                max: max.unwrap_or(i64::MAX),
+            },
+            "combo" => OptionKind::Combo {
+                default: default.unwrap_or_default(),
+                vars,
+            },
+            "button" => OptionKind::Button,
+            "string" => OptionKind::String {
+                default: default.unwrap_or_default(),
+            },
+            _ => return None,
+        };
+
+        Some(EngineOption {
+            name: name.trim().to_string(),
+            kind,
+        })
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct EngineInfo {
+    pub name: String,
+    pub author: String,
+    pub options: Vec<EngineOption>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Hash)]
+pub struct Go {
+    pub movetime: Option<Duration>,
+    pub depth: Option<u32>,
+    pub nodes: Option<u64>,
+    pub mate: Option<u32>,
+    pub wtime: Option<Duration>,
+    pub btime: Option<Duration>,
+    pub winc: Option<Duration>,
+    pub binc: Option<Duration>,
+    pub movestogo: Option<u32>,
+    pub infinite: bool,
+}
+
+impl Go {
+    pub fn movetime(movetime: Duration) -> Self {
+        Self {
+            movetime: Some(movetime),
+            ..Self::default()
+        }
+    }
+
+    pub fn depth(depth: u32) -> Self {
+        Self {
+            depth: Some(depth),
+            ..Self::default()
+        }
+    }
+
+    pub fn nodes(nodes: u64) -> Self {
+        Self {
+            nodes: Some(nodes),
+            ..Self::default()
+        }
+    }
+
+    pub fn infinite() -> Self {
+        Self {
+            infinite: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn clock(clock: &Clock, side: Color) -> Self {
+        let increment = |color: Color| {
+            let stage = clock.stage(color);
+            match clock.control(color).stages[stage].increment {
+                Increment::Fischer(increment) => Some(increment),
+                _ => None,
+            }
+        };
+
+        Self {
+            wtime: Some(clock.remaining(Color::White)),
+            btime: Some(clock.remaining(Color::Black)),
+            winc: increment(Color::White),
+            binc: increment(Color::Black),
+            movestogo: clock.moves_to_go(side),
+            ..Self::default()
+        }
+    }
+}
+
+impl fmt::Display for Go {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("go")?;
+
+        let millis = [
+            ("wtime", self.wtime),
+            ("btime", self.btime),
+            ("winc", self.winc),
+            ("binc", self.binc),
+            ("movetime", self.movetime),
+        ];
+        for (name, value) in millis {
+            if let Some(value) = value {
+                write!(f, " {name} {}", value.as_millis())?;
+            }
+        }
+        if let Some(movestogo) = self.movestogo {
+            write!(f, " movestogo {movestogo}")?;
+        }
+        if let Some(depth) = self.depth {
+            write!(f, " depth {depth}")?;
+        }
+        if let Some(nodes) = self.nodes {
+            write!(f, " nodes {nodes}")?;
+        }
+        if let Some(mate) = self.mate {
+            write!(f, " mate {mate}")?;
+        }
+        if self.infinite {
+            f.write_str(" infinite")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Hash)]
+pub struct EngineConfig {
+    pub path: PathBuf,
+    pub args: Vec<String>,
+    pub options: Vec<(String, Option<String>)>,
+}
+
+impl EngineConfig {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    #[must_use]
+    pub fn option(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.options.push((name.into(), Some(value.into())));
+        self
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Output {
+    Info(Info),
+    BestMove(BestMove),
+}
+
+pub struct Engine {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: Lines<BufReader<ChildStdout>>,
+    info: EngineInfo,
+}
+
+impl Engine {
+    pub async fn spawn(config: &EngineConfig) -> Result<Self, UciError> {
+        let mut child = Process::new(&config.path)
+            .args(&config.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let stdin = child.stdin.take().ok_or(UciError::Disconnected)?;
+        let stdout = child.stdout.take().ok_or(UciError::Disconnected)?;
+
+        let mut engine = Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout).lines(),
+            info: EngineInfo::default(),
+        };
+
+        engine.send("uci").await?;
+        loop {
+            let line = engine.read_line().await?;
+            if let Some(name) = line.strip_prefix("id name ") {
+                engine.info.name = name.trim().to_string();
+            } else if let Some(author) = line.strip_prefix("id author ") {
+                engine.info.author = author.trim().to_string();
+            } else if let Some(option) = EngineOption::parse(&line) {
+                engine.info.options.push(option);
+            } else if line.trim() == "uciok" {
+                break;
+            }
+        }
+
+        for (name, value) in &config.options {
+            engine.set_option(name, value.as_deref()).await?;
+        }
+        engine.is_ready().await?;
+
+        Ok(engine)
+    }
+
+    pub fn info(&self) -> &EngineInfo {
+        &self.info
+    }
+
+    pub async fn send(&mut self, command: &str) -> Result<(), UciError> {
+        self.stdin.write_all(command.as_bytes()).await?;
+        self.stdin.write_all(b"\n").await?;
+        self.stdin.flush().await?;
+        Ok(())
+    }
+
+    async fn read_line(&mut self) -> Result<String, UciError> {
+        self.stdout.next_line().await?.ok_or(UciError::Disconnected)
+    }
+
+    pub async fn set_option(&mut self, name: &str, value: Option<&str>) -> Result<(), UciError> {
+        match value {
+            Some(value) => {
+                self.send(&format!("setoption name {name} value {value}"))
+                    .await
+            }
+            None => self.send(&format!("setoption name {name}")).await,
+        }
+    }
+
+    pub async fn is_ready(&mut self) -> Result<(), UciError> {
+        self.send("isready").await?;
+        while self.read_line().await?.trim() != "readyok" {}
+        Ok(())
+    }
+
+    pub async fn new_game(&mut self) -> Result<(), UciError> {
+        self.send("ucinewgame").await?;
+        self.is_ready().await
+    }
+
+    pub async fn position(
+        &mut self,
+        root: &GameState,
+        moves: &[ChessMove],
+    ) -> Result<(), UciError> {
+        let mut command = if *root == GameState::default() {
+            String::from("position startpos")
+        } else {
+            format!("position fen {}", root.to_fen())
+        };
+        if !moves.is_empty() {
+            command.push_str(" moves");
+            for mv in moves {
+                command.push_str(&format!(" {mv}"));
+            }
+        }
+        self.send(&command).await
+    }
+
+    pub async fn go(&mut self, go: &Go) -> Result<(), UciError> {
+        self.send(&go.to_string()).await
+    }
+
+    pub async fn stop(&mut self) -> Result<(), UciError> {
+        self.send("stop").await
+    }
+
+    pub async fn quit(mut self) -> Result<(), UciError> {
+        self.send("quit").await?;
+        self.child.wait().await?;
+        Ok(())
+    }
+
+    pub async fn next_output(&mut self) -> Result<Output, UciError> {
+        loop {
+            let line = self.read_line().await?;
+            if let Some(info) = Info::parse(&line) {
+                return Ok(Output::Info(info));
+            }
+            if let Some(best) = BestMove::parse(&line) {
+                return Ok(Output::BestMove(best));
+            }
+        }
+    }
+
+    async fn execute(&mut self, command: Command) -> Result<(), UciError> {
+        match command {
+            Command::SetOption(name, value) => self.set_option(&name, value.as_deref()).await,
+            Command::NewGame => self.new_game().await,
+            Command::Position(root, moves) => self.position(&root, &moves).await,
+            Command::Go(go) => self.go(&go).await,
+            Command::Stop => self.stop().await,
+            Command::Quit => self.send("quit").await,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    SetOption(String, Option<String>),
+    NewGame,
+    Position(GameState, Vec<ChessMove>),
+    Go(Go),
+    Stop,
+    Quit,
+}
+
+#[derive(Debug, Clone)]
+pub struct Connection(mpsc::UnboundedSender<Command>);
+
+impl Connection {
+    pub fn send(&self, command: Command) {
+        let _ = self.0.unbounded_send(command);
+    }
+
+    pub fn position(&self, root: GameState, moves: Vec<ChessMove>) {
+        self.send(Command::Position(root, moves));
+    }
+
+    pub fn go(&self, go: Go) {
+        self.send(Command::Go(go));
+    }
+
+    pub fn stop(&self) {
+        self.send(Command::Stop);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Event {
+    Ready(Connection, EngineInfo),
+    Info(Info),
+    BestMove(BestMove),
+    Error(UciError),
+}
+
+impl From<Output> for Event {
+    fn from(output: Output) -> Self {
+        match output {
+            Output::Info(info) => Event::Info(info),
+            Output::BestMove(best) => Event::BestMove(best),
+        }
+    }
+}
+
+pub fn connect(config: EngineConfig) -> Subscription<Event> {
+    Subscription::run_with(config, |config| worker(config.clone()))
+}
+
+pub fn search(config: EngineConfig, root: GameState, moves: Vec<ChessMove>, go: Go) -> Task<Event> {
+    Task::stream(iced::stream::channel(100, async move |mut output| {
+        let result = async {
+            let mut engine = Engine::spawn(&config).await?;
+            engine.position(&root, &moves).await?;
+            engine.go(&go).await?;
+
+            loop {
+                let next = engine.next_output().await?;
+                let done = matches!(next, Output::BestMove(_));
+                let _ = output.send(next.into()).await;
+                if done {
+                    return engine.quit().await;
+                }
+            }
+        };
+
+        if let Err(error) = result.await {
+            let _ = output.send(Event::Error(error)).await;
+        }
+    }))
+}
+
+fn worker(config: EngineConfig) -> impl Stream<Item = Event> {
+    iced::stream::channel(100, async move |mut output| {
+        let mut engine = match Engine::spawn(&config).await {
+            Ok(engine) => engine,
+            Err(error) => {
+                let _ = output.send(Event::Error(error)).await;
+                return;
+            }
+        };
+
+        let (sender, mut receiver) = mpsc::unbounded();
+        let info = engine.info().clone();
+        let _ = output.send(Event::Ready(Connection(sender), info)).await;
+
+        loop {
+            let result = tokio::select! {
+                command = receiver.next() => match command {
+                    Some(Command::Quit) | None => {
+                        let _ = engine.quit().await;
+                        return;
+                    }
+                    Some(command) => engine.execute(command).await.map(|_| None),
+                },
+                next = engine.next_output() => next.map(Some),
+            };
+
+            match result {
+                Ok(Some(next)) => {
+                    let _ = output.send(next.into()).await;
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    let _ = output.send(Event::Error(error)).await;
+                    return;
+                }
+            }
+        }
+    })
+}
+
+fn parse_move(text: &str) -> Option<ChessMove> {
+    let mut chars = text.chars();
+    let mut square = || {
+        let file = chars.next().and_then(char_file)?;
+        let rank = chars.next().and_then(char_rank)?;
+        Some(Square::make_square(rank, file))
+    };
+    let source = square()?;
+    let dest = square()?;
+    let promotion = match chars.next() {
+        Some(c) => Some(char_piece(c)?),
+        None => None,
+    };
+    Some(ChessMove::new(source, dest, promotion))
+}
@@ -2,5 +2,23 @@ pub mod helpers;
 #[allow(unused_imports)]
 pub use helpers::*;
 
+pub mod captured_pieces;
+pub use captured_pieces::CapturedPieces;
+
 pub mod chess_board;
 pub use chess_board::ChessBoard;
+
+pub mod chess_clock;
+pub use chess_clock::ChessClock;
+
+pub mod eval_bar;
+pub use eval_bar::EvalBar;
+
+pub mod game_controls;
+pub use game_controls::GameControls;
+
+pub mod notation_panel;
+pub use notation_panel::NotationPanel;
+
+pub mod setup_panel;
+pub use setup_panel::SetupPanel;
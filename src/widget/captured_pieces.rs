@@ -0,0 +1,233 @@
+use chess::{ALL_COLORS, ALL_PIECES, Board, Color, Piece};
+use iced::{
+    Element, Font, Length, Pixels, Point, Rectangle, Renderer, Size,
+    advanced::{
+        Layout, Renderer as _, Widget,
+        graphics::geometry::Renderer as _,
+        image,
+        layout::{Limits, Node},
+        renderer,
+        widget::Tree,
+    },
+    alignment,
+    mouse::Cursor,
+    widget::{canvas, text},
+};
+
+use crate::{
+    chess::{GameState, GameTree, NodeId},
+    piece_set::PieceSet,
+    style::captured_pieces::Catalog,
+};
+
+const VALUES: [i32; 6] = [1, 3, 3, 5, 9, 0];
+const START: [u8; 6] = [8, 2, 2, 2, 1, 1];
+const ORDER: [Piece; 5] = [
+    Piece::Pawn,
+    Piece::Knight,
+    Piece::Bishop,
+    Piece::Rook,
+    Piece::Queen,
+];
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct Material {
+    captured: [[u8; 6]; 2],
+    balance: i32,
+}
+
+impl Material {
+    pub fn from_state(state: &GameState) -> Self {
+        let board = &state.board;
+        let mut captured = [[0; 6]; 2];
+
+        for color in ALL_COLORS {
+            let counts = ALL_PIECES
+                .map(|piece| (board.pieces(piece) & board.color_combined(color)).popcnt() as u8);
+            let promoted: u8 = (1..5).map(|i| counts[i].saturating_sub(START[i])).sum();
+
+            let mut lost: [u8; 6] = std::array::from_fn(|i| START[i].saturating_sub(counts[i]));
+            lost[Piece::Pawn.to_index()] = lost[Piece::Pawn.to_index()].saturating_sub(promoted);
+            captured[color.to_index()] = lost;
+        }
+
+        Self {
+            captured,
+            balance: balance(board),
+        }
+    }
+
+    pub fn from_history<'a>(history: impl IntoIterator<Item = &'a GameState>) -> Self {
+        let mut history = history.into_iter();
+        let Some(root) = history.next() else {
+            return Self::default();
+        };
+
+        let mut material = Self::from_state(root);
+        let mut last = root;
+        for state in history {
+            if let Some(capture) = state.annotation.and_then(|ant| ant.capture) {
+                let victim = state.board.side_to_move();
+                material.captured[victim.to_index()][capture.to_index()] += 1;
+            }
+            last = state;
+        }
+        material.balance = balance(&last.board);
+
+        material
+    }
+
+    pub fn from_tree(tree: &GameTree, id: NodeId) -> Self {
+        Self::from_history(tree.path(id).into_iter().filter_map(|id| tree.state(id)))
+    }
+
+    pub fn captured(&self, color: Color) -> [u8; 6] {
+        self.captured[color.to_index()]
+    }
+
+    pub fn advantage(&self, color: Color) -> i32 {
+        match color {
+            Color::White => self.balance,
+            Color::Black => -self.balance,
+        }
+    }
+}
+
+fn balance(board: &Board) -> i32 {
+    ALL_PIECES
+        .iter()
+        .map(|&piece| {
+            let pieces = board.pieces(piece);
+            let white = (pieces & board.color_combined(Color::White)).popcnt() as i32;
+            let black = (pieces & board.color_combined(Color::Black)).popcnt() as i32;
+            (white - black) * VALUES[piece.to_index()]
+        })
+        .sum()
+}
+
+pub struct CapturedPieces<Theme: Catalog> {
+    width: Length,
+    height: Length,
+    class: Theme::Class<'static>,
+    material: Material,
+    color: Color,
+    piece_set: PieceSet,
+}
+
+impl<Theme> CapturedPieces<Theme>
+where
+    Theme: Catalog,
+{
+    pub fn new(material: Material, color: Color) -> Self {
+        Self {
+            width: Length::Fill,
+            height: Length::Fixed(24.0),
+            class: Theme::default(),
+            material,
+            color,
+            piece_set: PieceSet::default(),
+        }
+    }
+
+    pub fn from_tree(tree: &GameTree, color: Color) -> Self {
+        Self::new(Material::from_tree(tree, tree.cursor()), color)
+    }
+
+    #[must_use]
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    #[must_use]
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    #[must_use]
+    pub fn piece_set(mut self, piece_set: PieceSet) -> Self {
+        self.piece_set = piece_set;
+        self
+    }
+}
+
+impl<Message, Theme> Widget<Message, Theme, Renderer> for CapturedPieces<Theme>
+where
+    Theme: Catalog,
+{
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, self.height)
+    }
+
+    fn layout(&mut self, _tree: &mut Tree, _renderer: &Renderer, limits: &Limits) -> Node {
+        Node::new(limits.resolve(self.width, self.height, Size::ZERO))
+    }
+
+    fn draw(
+        &self,
+        _state: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let style = theme.style(&self.class);
+
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let size = bounds.size();
+        let tile = Size::new(size.height, size.height);
+
+        frame.fill_rectangle(Point::ORIGIN, size, style.background);
+
+        let opponent = !self.color;
+        let captured = self.material.captured(opponent);
+        let mut x = 0.0;
+        for piece in ORDER {
+            let count = captured[piece.to_index()];
+            if count == 0 {
+                continue;
+            }
+            for _ in 0..count {
+                frame.draw_image(
+                    Rectangle::new(Point::new(x, 0.0), tile),
+                    image::Image::new(self.piece_set.get(piece, opponent, tile.width)),
+                );
+                x += tile.width * 0.4;
+            }
+            x += tile.width * 0.7;
+        }
+
+        let advantage = self.material.advantage(self.color);
+        if advantage > 0 {
+            frame.fill_text(canvas::Text {
+                content: format!("+{advantage}"),
+                position: Point::new(x + tile.width * 0.1, size.height / 2.0),
+                color: style.font,
+                size: Pixels(size.height * 0.55),
+                font: Font::default(),
+                align_x: text::Alignment::Left,
+                align_y: alignment::Vertical::Center,
+                ..canvas::Text::default()
+            });
+        }
+
+        let geometry = frame.into_geometry();
+        renderer.with_translation(bounds.position() - Point::ORIGIN, |renderer| {
+            renderer.draw_geometry(geometry);
+        });
+    }
+}
+
+impl<'a, Message, Theme> From<CapturedPieces<Theme>> for Element<'a, Message, Theme, Renderer>
+where
+    Theme: 'a + Catalog,
+    Message: 'a,
+{
+    fn from(captured: CapturedPieces<Theme>) -> Self {
+        Element::new(captured)
+    }
+}
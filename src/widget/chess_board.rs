@@ -1,7 +1,15 @@
+pub mod animation;
+pub mod cursor;
+pub mod editor;
+pub mod input;
 pub mod overlay;
+pub mod premove;
 pub mod render;
+pub mod shape;
 pub mod sound;
 
+use std::time::Duration;
+
 use chess::ChessMove;
 
 use iced::{
@@ -12,7 +20,8 @@ use iced::{
         layout::{Limits, Node},
         renderer,
         widget::{
-            Tree,
+            Operation, Tree,
+            operation::Focusable,
             tree::{self, Tag},
         },
     },
@@ -20,19 +29,48 @@ use iced::{
     widget::canvas::Cache,
 };
 
-use crate::{chess::GameState, style::chess_board::Catalog};
+use crate::{
+    chess::{BoardRole, GameOutcome, GameState},
+    piece_set::PieceSet,
+    setup::Setup,
+    style::chess_board::Catalog,
+    widget::game_controls::Control,
+};
 
+use animation::Easing;
+use cursor::Focus;
 use overlay::Overlay;
 use render::ChessBoardRenderer;
+use shape::Shape;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct BState {
     pub game: GameState,
     pub flipped: bool,
+    pub outcome: Option<GameOutcome>,
+    pub auto_queen: bool,
+    pub role: BoardRole,
+    pub chain_premoves: bool,
+    pub keyboard: bool,
+    pub flip_key: Option<char>,
+    pub animation: Duration,
+    pub easing: Easing,
+    pub setup: Option<Setup>,
+}
+
+impl BState {
+    pub fn is_over(&self) -> bool {
+        self.outcome.is_some() || self.game.status().is_over()
+    }
 }
 
 pub struct Messages<Message> {
     on_move: Option<Box<dyn Fn(ChessMove) -> Message>>,
+    on_premove: Option<Box<dyn Fn(Vec<ChessMove>) -> Message>>,
+    on_control: Option<Box<dyn Fn(Control) -> Message>>,
+    on_focus: Option<Box<dyn Fn(Option<Focus>) -> Message>>,
+    on_edit: Option<Box<dyn Fn(Setup) -> Message>>,
+    on_shapes_changed: Option<Box<dyn Fn(Vec<Shape>) -> Message>>,
 }
 
 pub struct ChessBoard<Message, Theme: Catalog> {
@@ -40,6 +78,9 @@ pub struct ChessBoard<Message, Theme: Catalog> {
     height: Length,
     class: Theme::Class<'static>,
     state: BState,
+    shapes: Vec<Shape>,
+    drawings: Option<Vec<Shape>>,
+    piece_set: PieceSet,
     message: Messages<Message>,
 }
 
@@ -52,11 +93,40 @@ where
             width: Length::Fill,
             height: Length::Fill,
             class: Theme::default(),
-            state: BState { game, flipped },
-            message: Messages { on_move: None },
+            state: BState {
+                game,
+                flipped,
+                outcome: None,
+                auto_queen: false,
+                role: BoardRole::Analyst,
+                chain_premoves: false,
+                keyboard: true,
+                flip_key: Some('f'),
+                animation: animation::DURATION,
+                easing: Easing::default(),
+                setup: None,
+            },
+            shapes: Vec::new(),
+            drawings: None,
+            piece_set: PieceSet::default(),
+            message: Messages {
+                on_move: None,
+                on_premove: None,
+                on_control: None,
+                on_focus: None,
+                on_edit: None,
+                on_shapes_changed: None,
+            },
         }
     }
 
+    pub fn editor(setup: Setup, flipped: bool) -> Self {
+        let mut board = Self::new(GameState::default(), flipped);
+        board.state.setup = Some(setup);
+        board.state.keyboard = false;
+        board
+    }
+
     #[must_use]
     pub fn width(mut self, width: impl Into<Length>) -> Self {
         self.width = width.into();
@@ -69,6 +139,79 @@ where
         self
     }
 
+    /// Stops the board from taking moves once the game is over. Keep this in sync with the
+    /// game's outcome, such as `GameTree::outcome`; checkmate, stalemate and the automatic
+    /// draws lock the board on their own.
+    #[must_use]
+    pub fn outcome(mut self, outcome: Option<GameOutcome>) -> Self {
+        self.state.outcome = outcome;
+        self
+    }
+
+    #[must_use]
+    pub fn auto_queen(mut self, auto_queen: bool) -> Self {
+        self.state.auto_queen = auto_queen;
+        self
+    }
+
+    #[must_use]
+    pub fn role(mut self, role: BoardRole) -> Self {
+        self.state.role = role;
+        self
+    }
+
+    #[must_use]
+    pub fn chain_premoves(mut self, chain_premoves: bool) -> Self {
+        self.state.chain_premoves = chain_premoves;
+        self
+    }
+
+    /// Handles keys while the board has focus. A click on the board focuses it and a click
+    /// elsewhere blurs it; focusing it with an operation such as `focus_next` also starts the
+    /// square cursor.
+    ///
+    /// Left and Right step through history, Up/Home and Down/End jump to either end and the
+    /// [`flip_key`](Self::flip_key) flips the board. Any other typing is read as a SAN or UCI
+    /// move and submitted once it matches a single legal move. While the square cursor is
+    /// shown, the arrow keys move it, Enter or Space activates its square and Escape hides it.
+    #[must_use]
+    pub fn keyboard(mut self, keyboard: bool) -> Self {
+        self.state.keyboard = keyboard;
+        self
+    }
+
+    /// Sets the key that flips the board, `f` by default. A flip key that could start a typed
+    /// move for the side to move is read as part of the move instead.
+    #[must_use]
+    pub fn flip_key(mut self, flip_key: Option<char>) -> Self {
+        self.state.flip_key = flip_key;
+        self
+    }
+
+    #[must_use]
+    pub fn shapes(mut self, shapes: Vec<Shape>) -> Self {
+        self.shapes = shapes;
+        self
+    }
+
+    #[must_use]
+    pub fn drawings(mut self, drawings: Vec<Shape>) -> Self {
+        self.drawings = Some(drawings);
+        self
+    }
+
+    #[must_use]
+    pub fn animation(mut self, animation: Duration) -> Self {
+        self.state.animation = animation;
+        self
+    }
+
+    #[must_use]
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.state.easing = easing;
+        self
+    }
+
     #[must_use]
     pub fn on_move_maybe<F>(mut self, on_move: Option<F>) -> Self
     where
@@ -77,6 +220,74 @@ where
         self.message.on_move = on_move.map(|f| Box::new(f) as Box<dyn Fn(ChessMove) -> Message>);
         self
     }
+
+    #[must_use]
+    pub fn on_premove<F>(self, on_premove: F) -> Self
+    where
+        F: 'static + Fn(Vec<ChessMove>) -> Message,
+    {
+        self.on_premove_maybe(Some(on_premove))
+    }
+
+    #[must_use]
+    pub fn on_premove_maybe<F>(mut self, on_premove: Option<F>) -> Self
+    where
+        F: 'static + Fn(Vec<ChessMove>) -> Message,
+    {
+        self.message.on_premove =
+            on_premove.map(|f| Box::new(f) as Box<dyn Fn(Vec<ChessMove>) -> Message>);
+        self
+    }
+
+    #[must_use]
+    pub fn on_control<F>(mut self, on_control: F) -> Self
+    where
+        F: 'static + Fn(Control) -> Message,
+    {
+        self.message.on_control = Some(Box::new(on_control));
+        self
+    }
+
+    #[must_use]
+    pub fn on_focus<F>(mut self, on_focus: F) -> Self
+    where
+        F: 'static + Fn(Option<Focus>) -> Message,
+    {
+        self.message.on_focus = Some(Box::new(on_focus));
+        self
+    }
+
+    #[must_use]
+    pub fn piece_set(mut self, piece_set: PieceSet) -> Self {
+        self.piece_set = piece_set;
+        self
+    }
+
+    #[must_use]
+    pub fn on_edit<F>(mut self, on_edit: F) -> Self
+    where
+        F: 'static + Fn(Setup) -> Message,
+    {
+        self.message.on_edit = Some(Box::new(on_edit));
+        self
+    }
+
+    #[must_use]
+    pub fn on_shapes_changed<F>(mut self, on_shapes_changed: F) -> Self
+    where
+        F: 'static + Fn(Vec<Shape>) -> Message,
+    {
+        self.message.on_shapes_changed = Some(Box::new(on_shapes_changed));
+        self
+    }
+
+    fn board_bounds(&self, bounds: Rectangle) -> Rectangle {
+        if self.state.setup.is_some() {
+            editor::board_bounds(bounds)
+        } else {
+            bounds
+        }
+    }
 }
 
 impl<'a, Message, Theme> Widget<Message, Theme, Renderer> for ChessBoard<Message, Theme>
@@ -89,7 +300,13 @@ where
     }
 
     fn state(&self) -> tree::State {
-        tree::State::new(State::new(self.state))
+        let mut state = State::new(self.state, self.shapes.clone());
+        state.piece_set = self.piece_set.clone();
+        if let Some(drawings) = &self.drawings {
+            state.overlay.shapes = drawings.clone();
+            state.drawings = Some(drawings.clone());
+        }
+        tree::State::new(state)
     }
 
     fn diff(&self, tree: &mut Tree) {
@@ -97,7 +314,7 @@ where
 
         if self.state != wstate.state {
             wstate.cache.board.clear();
-            wstate.cache.board.clear();
+            wstate.cache.board_overlay.clear();
             wstate.cache.pieces.clear();
             wstate.cache.overlay.clear();
 
@@ -105,14 +322,54 @@ where
 
             wstate.state = self.state;
         }
+
+        if self.shapes != wstate.shapes {
+            wstate.cache.board_overlay.clear();
+            wstate.cache.overlay.clear();
+            wstate.shapes = self.shapes.clone();
+        }
+
+        if self.piece_set != wstate.piece_set {
+            wstate.cache.board.clear();
+            wstate.cache.pieces.clear();
+            wstate.cache.drag.clear();
+            wstate.cache.overlay.clear();
+            wstate.piece_set = self.piece_set.clone();
+        }
+
+        if self.drawings != wstate.drawings {
+            if let Some(drawings) = &self.drawings {
+                wstate.overlay.shapes = drawings.clone();
+            }
+            wstate.cache.board_overlay.clear();
+            wstate.cache.overlay.clear();
+            wstate.drawings = self.drawings.clone();
+        }
     }
 
     fn size(&self) -> Size<Length> {
         Size::new(self.width, self.height)
     }
 
+    fn operate(
+        &mut self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        _renderer: &Renderer,
+        operation: &mut dyn Operation,
+    ) {
+        if self.state.keyboard {
+            let wstate: &mut State = tree.state.downcast_mut();
+            operation.focusable(None, layout.bounds(), wstate);
+        }
+    }
+
     fn layout(&mut self, _tree: &mut Tree, _renderer: &Renderer, limits: &Limits) -> Node {
         let resolved = limits.resolve(self.width, self.height, Size::ZERO);
+        if self.state.setup.is_some() {
+            let tile = editor::tile_size(Rectangle::with_size(resolved));
+            return Node::new(Size::new(tile * 8.0, tile * 10.0));
+        }
         let side = resolved.width.min(resolved.height);
         Node::new(Size::new(side, side))
     }
@@ -128,6 +385,12 @@ where
         let bounds = layout.bounds();
         let wstate: &State = state.state.downcast_ref();
 
+        if wstate.state.setup.is_some() {
+            return wstate
+                .overlay
+                .editor_interaction(bounds, cursor, &wstate.state);
+        }
+
         wstate
             .overlay
             .mouse_interaction(bounds, cursor, &wstate.state)
@@ -147,6 +410,19 @@ where
         let bounds = layout.bounds();
         let wstate: &mut State = state.state.downcast_mut();
 
+        if self.state.setup.is_some() {
+            wstate.overlay.on_event_editor(
+                event,
+                bounds,
+                cursor,
+                &self.state,
+                &self.message,
+                &mut wstate.cache,
+                shell,
+            );
+            return;
+        }
+
         wstate.overlay.on_event(
             event,
             bounds,
@@ -172,26 +448,42 @@ where
         let wstate: &State = state.state.downcast_ref();
         let style = theme.style(&self.class);
 
-        let cbrenderer = ChessBoardRenderer::new(style, self.state, bounds);
+        let board = self.board_bounds(bounds);
+        let offset = board.position() - bounds.position();
+        let cbrenderer = ChessBoardRenderer::new(style, self.state, self.piece_set.clone(), board);
 
         let geometrys = vec![
             wstate.cache.board.draw(renderer, bounds.size(), |frame| {
+                if self.state.setup.is_some() {
+                    cbrenderer.draw_palette(frame, Rectangle::with_size(bounds.size()));
+                }
+                frame.translate(offset);
                 cbrenderer.draw_board(frame, &wstate.overlay);
             }),
             wstate
                 .cache
                 .board_overlay
                 .draw(renderer, bounds.size(), |frame| {
+                    frame.translate(offset);
                     cbrenderer.draw_board_overlay(frame, &wstate.overlay);
+                    cbrenderer.draw_shape_fills(frame, &wstate.shapes);
+                    cbrenderer.draw_shape_fills(frame, &wstate.overlay.shapes);
                 }),
             wstate.cache.pieces.draw(renderer, bounds.size(), |frame| {
+                frame.translate(offset);
                 cbrenderer.draw_pieces(frame, &wstate.overlay);
             }),
             wstate.cache.drag.draw(renderer, bounds.size(), |frame| {
+                frame.translate(offset);
                 cbrenderer.draw_drag(frame, &wstate.overlay);
             }),
             wstate.cache.overlay.draw(renderer, bounds.size(), |frame| {
-                cbrenderer.draw_arrows(frame, &wstate.overlay);
+                frame.translate(offset);
+                cbrenderer.draw_shapes(frame, &wstate.shapes);
+                cbrenderer.draw_shapes(frame, &wstate.overlay.shapes);
+                cbrenderer.draw_promotion(frame, &wstate.overlay);
+                cbrenderer.draw_focus(frame, &wstate.overlay);
+                cbrenderer.draw_input(frame, &wstate.overlay);
             }),
         ];
 
@@ -216,18 +508,43 @@ pub struct State {
     pub(crate) overlay: Overlay,
     pub(crate) cache: Caches,
     pub(crate) state: BState,
+    pub(crate) shapes: Vec<Shape>,
+    pub(crate) drawings: Option<Vec<Shape>>,
+    pub(crate) piece_set: PieceSet,
 }
 
 impl State {
-    pub fn new(state: BState) -> Self {
+    pub fn new(state: BState, shapes: Vec<Shape>) -> Self {
         Self {
             overlay: Overlay::new(),
             cache: Caches::default(),
             state,
+            shapes,
+            drawings: None,
+            piece_set: PieceSet::default(),
         }
     }
 }
 
+impl Focusable for State {
+    fn is_focused(&self) -> bool {
+        self.overlay.is_focused()
+    }
+
+    fn focus(&mut self) {
+        self.overlay.gain_focus(&self.state);
+        self.cache.board_overlay.clear();
+        self.cache.overlay.clear();
+    }
+
+    fn unfocus(&mut self) {
+        self.overlay.lose_focus();
+        self.cache.board_overlay.clear();
+        self.cache.pieces.clear();
+        self.cache.overlay.clear();
+    }
+}
+
 impl<'a, Message, Theme> From<ChessBoard<Message, Theme>> for Element<'a, Message, Theme, Renderer>
 where
     Theme: 'a + Catalog,
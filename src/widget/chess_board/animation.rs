@@ -0,0 +1,161 @@
+use std::time::{Duration, Instant};
+
+use chess::{ALL_SQUARES, Color, Piece, Square};
+use iced::Point;
+
+use super::premove::PieceMap;
+
+pub const DURATION: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    #[default]
+    EaseInOut,
+}
+
+impl Easing {
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut if t < 0.5 => 4.0 * t * t * t,
+            Easing::EaseInOut => 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Slide {
+    pub piece: Piece,
+    pub color: Color,
+    pub square: Square,
+    pub from: Point,
+    pub to: Point,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Fade {
+    pub piece: Piece,
+    pub color: Color,
+    pub at: Point,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Animation {
+    pub slides: Vec<Slide>,
+    pub fades: Vec<Fade>,
+    pub progress: f32,
+    started: Option<Instant>,
+}
+
+impl Animation {
+    pub fn plan(
+        old: &PieceMap,
+        new: &PieceMap,
+        old_flipped: bool,
+        new_flipped: bool,
+        skip: Option<Square>,
+    ) -> Option<Self> {
+        let mut vanished: Vec<Square> = ALL_SQUARES
+            .into_iter()
+            .filter(|sq| old[sq.to_index()].is_some() && old[sq.to_index()] != new[sq.to_index()])
+            .collect();
+
+        let mut slides = Vec::new();
+        for square in ALL_SQUARES {
+            let Some((piece, color)) = new[square.to_index()] else {
+                continue;
+            };
+
+            let source = if old[square.to_index()] == new[square.to_index()] {
+                Some(square)
+            } else {
+                let nearest = |source: (Piece, Color)| {
+                    vanished
+                        .iter()
+                        .enumerate()
+                        .filter(|&(_, sq)| old[sq.to_index()] == Some(source))
+                        .min_by_key(|&(_, &sq)| distance(sq, square))
+                        .map(|(index, _)| index)
+                };
+                // A promoted piece slides in from the pawn that became it.
+                let index = nearest((piece, color)).or_else(|| nearest((Piece::Pawn, color)));
+                index.map(|index| vanished.remove(index))
+            };
+
+            let Some(source) = source else {
+                continue;
+            };
+            let from = position(source, old_flipped);
+            let to = position(square, new_flipped);
+            if from != to && skip != Some(square) {
+                slides.push(Slide {
+                    piece,
+                    color,
+                    square,
+                    from,
+                    to,
+                });
+            }
+        }
+
+        let fades: Vec<Fade> = vanished
+            .into_iter()
+            .filter_map(|sq| {
+                let (piece, color) = old[sq.to_index()]?;
+                Some(Fade {
+                    piece,
+                    color,
+                    at: position(sq, old_flipped),
+                })
+            })
+            .collect();
+
+        if slides.is_empty() && fades.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            slides,
+            fades,
+            progress: 0.0,
+            started: None,
+        })
+    }
+
+    pub fn tick(&mut self, now: Instant, duration: Duration, easing: Easing) -> bool {
+        if duration.is_zero() {
+            self.progress = 1.0;
+            return true;
+        }
+        let started = *self.started.get_or_insert(now);
+        let t = now.duration_since(started).as_secs_f32() / duration.as_secs_f32();
+        self.progress = easing.apply(t);
+        t >= 1.0
+    }
+
+    pub fn is_sliding(&self, square: Square) -> bool {
+        self.slides.iter().any(|slide| slide.square == square)
+    }
+}
+
+pub fn position(square: Square, flipped: bool) -> Point {
+    let row = square.get_rank().to_index() as f32;
+    let col = square.get_file().to_index() as f32;
+    if flipped {
+        Point::new(7.0 - col, row)
+    } else {
+        Point::new(col, 7.0 - row)
+    }
+}
+
+fn distance(a: Square, b: Square) -> usize {
+    let files = a.get_file().to_index().abs_diff(b.get_file().to_index());
+    let ranks = a.get_rank().to_index().abs_diff(b.get_rank().to_index());
+    files * files + ranks * ranks
+}
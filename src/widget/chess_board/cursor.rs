@@ -0,0 +1,69 @@
+use std::fmt;
+
+use chess::{Color, File, Piece, Rank, Square};
+
+use super::premove::PieceMap;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Focus {
+    pub square: Square,
+    pub piece: Option<(Piece, Color)>,
+    pub selected: bool,
+    pub target: bool,
+}
+
+impl Focus {
+    pub fn new(pieces: &PieceMap, square: Square, selected: bool, target: bool) -> Self {
+        Self {
+            square,
+            piece: pieces[square.to_index()],
+            selected,
+            target,
+        }
+    }
+}
+
+impl fmt::Display for Focus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, ", self.square)?;
+        match self.piece {
+            Some((piece, color)) => write!(f, "{} {}", color_name(color), piece_name(piece))?,
+            None => f.write_str("empty")?,
+        }
+        if self.selected {
+            f.write_str(", selected")?;
+        }
+        if self.target {
+            f.write_str(", legal destination")?;
+        }
+        Ok(())
+    }
+}
+
+pub fn step(square: Square, right: i32, up: i32, flipped: bool) -> Square {
+    let (right, up) = if flipped { (-right, -up) } else { (right, up) };
+    let file = (square.get_file().to_index() as i32 + right).clamp(0, 7);
+    let rank = (square.get_rank().to_index() as i32 + up).clamp(0, 7);
+    Square::make_square(
+        Rank::from_index(rank as usize),
+        File::from_index(file as usize),
+    )
+}
+
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::White => "white",
+        Color::Black => "black",
+    }
+}
+
+fn piece_name(piece: Piece) -> &'static str {
+    match piece {
+        Piece::Pawn => "pawn",
+        Piece::Knight => "knight",
+        Piece::Bishop => "bishop",
+        Piece::Rook => "rook",
+        Piece::Queen => "queen",
+        Piece::King => "king",
+    }
+}
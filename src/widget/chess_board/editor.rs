@@ -0,0 +1,53 @@
+use chess::{Color, Piece};
+use iced::{Point, Rectangle, Size};
+
+pub const SPARE_PIECES: [Piece; 6] = [
+    Piece::King,
+    Piece::Queen,
+    Piece::Rook,
+    Piece::Bishop,
+    Piece::Knight,
+    Piece::Pawn,
+];
+
+pub fn tile_size(bounds: Rectangle) -> f32 {
+    (bounds.width / 8.0).min(bounds.height / 10.0)
+}
+
+pub fn board_bounds(bounds: Rectangle) -> Rectangle {
+    let tile = tile_size(bounds);
+    Rectangle::new(
+        Point::new(bounds.x, bounds.y + tile),
+        Size::new(tile * 8.0, tile * 8.0),
+    )
+}
+
+pub fn palette(bounds: Rectangle, flipped: bool) -> Vec<(Rectangle, Piece, Color)> {
+    let tile = tile_size(bounds);
+    let (top, bottom) = if flipped {
+        (Color::White, Color::Black)
+    } else {
+        (Color::Black, Color::White)
+    };
+
+    [(bounds.y, top), (bounds.y + tile * 9.0, bottom)]
+        .into_iter()
+        .flat_map(|(y, color)| {
+            SPARE_PIECES
+                .into_iter()
+                .enumerate()
+                .map(move |(index, piece)| {
+                    let x = bounds.x + (index + 1) as f32 * tile;
+                    let slot = Rectangle::new(Point::new(x, y), Size::new(tile, tile));
+                    (slot, piece, color)
+                })
+        })
+        .collect()
+}
+
+pub fn spare_at(bounds: Rectangle, flipped: bool, position: Point) -> Option<(Piece, Color)> {
+    palette(bounds, flipped)
+        .into_iter()
+        .find(|(slot, _, _)| slot.contains(position))
+        .map(|(_, piece, color)| (piece, color))
+}
@@ -0,0 +1,104 @@
+use chess::{ChessMove, MoveGen};
+
+use crate::chess::GameState;
+
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct MoveInput {
+    text: String,
+}
+
+impl MoveInput {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+    }
+
+    pub fn push(&mut self, c: char) -> bool {
+        let accepted = matches!(c, 'a'..='h' | '1'..='8' | 'K' | 'Q' | 'R' | 'B' | 'N')
+            || matches!(
+                c,
+                'q' | 'r' | 'n' | 'O' | 'o' | '0' | 'x' | '+' | '#' | '=' | '-'
+            );
+        if accepted {
+            self.text.push(c);
+        }
+        accepted
+    }
+
+    pub fn pop(&mut self) -> Option<char> {
+        self.text.pop()
+    }
+
+    pub fn candidates(&self, game: &GameState) -> Vec<(ChessMove, bool)> {
+        let input = normalize(&self.text);
+        if input.is_empty() {
+            return Vec::new();
+        }
+        let uci_input = input.to_ascii_lowercase();
+
+        MoveGen::new_legal(&game.board)
+            .filter_map(|mv| {
+                let san = matches(&normalize(&game.san(mv)), &input);
+                let uci = mv.to_string();
+                let uci = uci
+                    .starts_with(&uci_input)
+                    .then_some(uci.len() == uci_input.len());
+                match (san, uci) {
+                    (None, None) => None,
+                    (san, uci) => Some((mv, san == Some(true) || uci == Some(true))),
+                }
+            })
+            .collect()
+    }
+
+    pub fn resolve(&self, game: &GameState) -> Option<ChessMove> {
+        match self.candidates(game)[..] {
+            [(mv, true)] => Some(mv),
+            _ => None,
+        }
+    }
+
+    pub fn exact(&self, game: &GameState) -> Option<ChessMove> {
+        let exact: Vec<_> = self
+            .candidates(game)
+            .into_iter()
+            .filter(|&(_, exact)| exact)
+            .collect();
+        match exact[..] {
+            [(mv, _)] => Some(mv),
+            _ => None,
+        }
+    }
+}
+
+fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|c| !matches!(c, 'x' | '+' | '#' | '=' | '-'))
+        .map(|c| match c {
+            '0' | 'o' => 'O',
+            c => c,
+        })
+        .collect()
+}
+
+fn matches(candidate: &str, input: &str) -> Option<bool> {
+    let mut candidate = candidate.chars();
+    let mut input = input.chars();
+    if candidate.next() != input.next() {
+        return None;
+    }
+
+    let candidate = candidate.as_str();
+    let input = input.as_str();
+    let prefix = candidate
+        .get(..input.len())
+        .is_some_and(|head| head.eq_ignore_ascii_case(input));
+    prefix.then_some(candidate.len() == input.len())
+}
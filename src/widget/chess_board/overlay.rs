@@ -1,11 +1,53 @@
-use chess::{BitBoard, BoardStatus, ChessMove, File, MoveGen, Piece, Rank, Square};
-use iced::{Point, Rectangle, advanced::Shell, mouse, widget::canvas};
+use std::{collections::HashMap, time::Instant};
+
+use chess::{BitBoard, ChessMove, Color, File, MoveGen, Piece, Rank, Square};
+use iced::{
+    Point, Rectangle,
+    advanced::Shell,
+    keyboard::{self, Key, key::Named},
+    mouse,
+    widget::canvas,
+    window,
+};
 
 use super::{
     BState, Caches, Messages,
+    animation::Animation,
+    cursor::{self, Focus},
+    editor,
+    input::MoveInput,
+    premove::{self, PieceMap, Premoves},
+    shape::{self, Brush, Mark, Shape},
     sound::{ChessBoardSound, SoundType},
 };
-use crate::chess::Move;
+use crate::{
+    chess::{BoardRole, Move},
+    widget::game_controls::Control,
+};
+
+pub const PROMOTION_PIECES: [Piece; 4] = [Piece::Queen, Piece::Knight, Piece::Rook, Piece::Bishop];
+
+pub fn promotion_choices_for(mv: ChessMove) -> Vec<(Square, Piece)> {
+    let dest = mv.get_dest();
+    let mut square = Some(dest);
+    PROMOTION_PIECES
+        .iter()
+        .map_while(|&piece| {
+            let sq = square?;
+            square = if dest.get_rank() == Rank::Eighth {
+                sq.down()
+            } else {
+                sq.up()
+            };
+            Some((sq, piece))
+        })
+        .collect()
+}
+
+pub fn pending_promotion(mv: ChessMove, auto_queen: bool) -> Option<ChessMove> {
+    (mv.get_promotion().is_some() && !auto_queen)
+        .then(|| ChessMove::new(mv.get_source(), mv.get_dest(), None))
+}
 
 #[derive(Clone)]
 pub struct Overlay {
@@ -13,9 +55,21 @@ pub struct Overlay {
     pub hints: Vec<ChessMove>,
     pub selected: Option<Square>,
     pub drag: Option<Point>,
-    pub highlight: BitBoard,
     pub anchor: Option<Square>,
-    pub arrows: Vec<ChessMove>,
+    pub shapes: Vec<Shape>,
+    drawings: HashMap<u64, Vec<Shape>>,
+    pub promotion: Option<ChessMove>,
+    pub premoves: Premoves,
+    pub input: MoveInput,
+    pub cursor: Option<Square>,
+    pub animation: Option<Animation>,
+    pub spare: Option<(Piece, Color)>,
+    dropped: Option<Square>,
+    modifiers: keyboard::Modifiers,
+    premove_ready: bool,
+    premoves_dropped: bool,
+    focused: bool,
+    focus_changed: bool,
 }
 
 impl Overlay {
@@ -25,9 +79,21 @@ impl Overlay {
             hints: Vec::new(),
             selected: None,
             drag: None,
-            highlight: BitBoard::default(),
             anchor: None,
-            arrows: Vec::new(),
+            shapes: Vec::new(),
+            drawings: HashMap::new(),
+            promotion: None,
+            premoves: Premoves::default(),
+            input: MoveInput::default(),
+            cursor: None,
+            animation: None,
+            spare: None,
+            dropped: None,
+            modifiers: keyboard::Modifiers::default(),
+            premove_ready: false,
+            premoves_dropped: false,
+            focused: false,
+            focus_changed: false,
         }
     }
 
@@ -35,11 +101,39 @@ impl Overlay {
         self.hints.clear();
         self.selected = None;
         self.drag = None;
+        self.promotion = None;
+        self.spare = None;
+    }
+
+    pub fn pieces(&self, state: &BState) -> PieceMap {
+        match state.setup {
+            Some(setup) => setup.pieces,
+            None => self.premoves.pieces(&state.game.board),
+        }
     }
 
     fn clear_overlay(&mut self) {
-        self.arrows.clear();
-        self.highlight = BitBoard::new(0);
+        self.shapes.clear();
+    }
+
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    pub fn gain_focus(&mut self, state: &BState) {
+        let board = &state.game.board;
+        self.focused = true;
+        self.cursor = Some(board.king_square(board.side_to_move()));
+        self.focus_changed = true;
+    }
+
+    pub fn lose_focus(&mut self) {
+        self.focused = false;
+        self.input.clear();
+        if self.cursor.take().is_some() {
+            self.clear_selection();
+            self.focus_changed = true;
+        }
     }
 }
 
@@ -73,20 +167,200 @@ impl Overlay {
     fn find_move(&self, sq: Square) -> Option<ChessMove> {
         self.hints.iter().find(|mv| mv.get_dest() == sq).cloned()
     }
+
+    pub fn promotion_choices(&self) -> Vec<(Square, Piece)> {
+        match self.promotion {
+            Some(mv) => promotion_choices_for(mv),
+            None => Vec::new(),
+        }
+    }
+
+    fn select<Message>(
+        &mut self,
+        square: Square,
+        state: &BState,
+        messages: &Messages<Message>,
+    ) -> bool {
+        let premove = Self::premove_color(state, messages);
+        let pieces = self.premoves.pieces(&state.game.board);
+
+        if pieces[square.to_index()].is_none() {
+            self.clear_selection();
+            return false;
+        }
+
+        self.hints.clear();
+        self.selected = Some(square);
+
+        if let Some(color) = premove {
+            if pieces[square.to_index()].is_some_and(|(_, c)| c == color) {
+                self.hints = self.premoves.dests(&state.game.board, square);
+            }
+        } else if messages.on_move.is_some() && !state.is_over() {
+            for mv in MoveGen::new_legal(&state.game.board) {
+                if mv.get_source() == square
+                    && (mv.get_promotion() == Some(Piece::Queen) || mv.get_promotion().is_none())
+                {
+                    self.hints.push(mv);
+                }
+            }
+        }
+
+        true
+    }
+
+    fn premove_color<Message>(state: &BState, messages: &Messages<Message>) -> Option<Color> {
+        match state.role {
+            BoardRole::Player(color)
+                if messages.on_premove.is_some()
+                    && !state.is_over()
+                    && state.game.board.side_to_move() != color =>
+            {
+                Some(color)
+            }
+            _ => None,
+        }
+    }
+
+    fn accepts_input<Message>(state: &BState, messages: &Messages<Message>) -> bool {
+        messages.on_move.is_some()
+            && !state.is_over()
+            && state.role.can_move(&state.game.board.side_to_move())
+            && Self::premove_color(state, messages).is_none()
+    }
+
+    fn publish_shapes<Message>(
+        &self,
+        messages: &Messages<Message>,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        if let Some(on_shapes_changed) = &messages.on_shapes_changed {
+            shell.publish((on_shapes_changed)(self.shapes.clone()));
+        }
+    }
+
+    fn publish_premoves<Message>(
+        &self,
+        messages: &Messages<Message>,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        if let Some(on_premove) = &messages.on_premove {
+            shell.publish((on_premove)(self.premoves.moves().to_vec()));
+        }
+    }
+
+    fn run_premove<Message>(
+        &mut self,
+        state: &BState,
+        messages: &Messages<Message>,
+        caches: &mut Caches,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        if std::mem::take(&mut self.premoves_dropped) {
+            self.publish_premoves(messages, shell);
+            caches.board_overlay.clear();
+            caches.pieces.clear();
+            shell.request_redraw();
+        }
+        if !self.premove_ready {
+            return;
+        }
+
+        let BoardRole::Player(color) = state.role else {
+            return;
+        };
+        if !state.is_over() && state.game.board.side_to_move() != color {
+            return;
+        }
+        self.premove_ready = false;
+
+        let premove = self.premoves.pop_front();
+        let legal = premove
+            .filter(|_| !state.is_over())
+            .and_then(|premove| premove::legal(&state.game.board, premove));
+
+        match (legal, &messages.on_move) {
+            (Some(mv), Some(on_move)) => shell.publish((on_move)(mv)),
+            _ => self.premoves.clear(),
+        }
+        self.publish_premoves(messages, shell);
+
+        caches.board_overlay.clear();
+        caches.pieces.clear();
+        shell.request_redraw();
+    }
+
+    fn submit<Message>(
+        &mut self,
+        mv: ChessMove,
+        state: &BState,
+        messages: &Messages<Message>,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        self.clear_selection();
+
+        if Self::premove_color(state, messages).is_some() {
+            self.premoves.push(mv, state.chain_premoves);
+            self.sound.play(SoundType::Premove);
+            self.publish_premoves(messages, shell);
+            shell.request_redraw();
+            return;
+        }
+
+        if let Some(pending) = pending_promotion(mv, state.auto_queen) {
+            self.promotion = Some(pending);
+            shell.request_redraw();
+            return;
+        }
+
+        if let Some(on_move) = &messages.on_move {
+            shell.publish((on_move)(mv));
+        }
+    }
 }
 
 impl Overlay {
     pub fn on_diff(&mut self, old: &BState, new: &BState) {
+        let dropped = self.dropped.take();
+        if old.game != new.game || old.flipped != new.flipped {
+            self.animation = None;
+            if !new.animation.is_zero() {
+                let pieces = |state: &BState| match state.setup {
+                    Some(setup) => setup.pieces,
+                    None => Premoves::default().pieces(&state.game.board),
+                };
+                self.animation = Animation::plan(
+                    &pieces(old),
+                    &pieces(new),
+                    old.flipped,
+                    new.flipped,
+                    dropped,
+                );
+            }
+        }
+
         if old.game == new.game {
             return;
         }
 
         self.clear_selection();
-        self.clear_overlay();
+        self.restore_drawings(old, new);
+        self.input.clear();
+
+        self.premove_ready = false;
+        if !self.premoves.is_empty() {
+            let side = new.game.board.side_to_move();
+            if !premove::follows(&old.game.board, &new.game.board) {
+                self.premoves.clear();
+                self.premoves_dropped = true;
+            } else if new.role == BoardRole::Player(side) {
+                self.premove_ready = true;
+            }
+        }
 
         let board = new.game.board;
 
-        if board.status() == BoardStatus::Checkmate {
+        if new.game.status().is_over() {
             self.sound.play(SoundType::GameEnd);
         }
 
@@ -107,6 +381,20 @@ impl Overlay {
         }
     }
 
+    fn restore_drawings(&mut self, old: &BState, new: &BState) {
+        let shapes = std::mem::take(&mut self.shapes);
+        let old_hash = old.game.board.get_hash();
+        if shapes.is_empty() {
+            self.drawings.remove(&old_hash);
+        } else {
+            self.drawings.insert(old_hash, shapes);
+        }
+
+        if let Some(shapes) = self.drawings.get(&new.game.board.get_hash()) {
+            self.shapes = shapes.clone();
+        }
+    }
+
     pub fn mouse_interaction(
         &self,
         bounds: Rectangle,
@@ -117,6 +405,16 @@ impl Overlay {
             return mouse::Interaction::Grabbing;
         }
 
+        if self.promotion.is_some() {
+            let choice = Self::cursor_square(bounds, cursor, state.flipped)
+                .is_some_and(|sq| self.promotion_choices().iter().any(|(s, _)| *s == sq));
+            return if choice {
+                mouse::Interaction::Pointer
+            } else {
+                mouse::Interaction::default()
+            };
+        }
+
         if let Some(sq) = Self::cursor_square(bounds, cursor, state.flipped) {
             if state.game.board.piece_on(sq).is_some() {
                 return mouse::Interaction::Grab;
@@ -125,6 +423,29 @@ impl Overlay {
 
         mouse::Interaction::default()
     }
+
+    pub fn editor_interaction(
+        &self,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+        state: &BState,
+    ) -> mouse::Interaction {
+        if self.drag.is_some() {
+            return mouse::Interaction::Grabbing;
+        }
+
+        let board = editor::board_bounds(bounds);
+        let spare = cursor
+            .position()
+            .and_then(|pos| editor::spare_at(bounds, state.flipped, pos));
+        let piece = Self::cursor_square(board, cursor, state.flipped)
+            .and_then(|sq| self.pieces(state)[sq.to_index()]);
+        if spare.is_some() || piece.is_some() {
+            mouse::Interaction::Grab
+        } else {
+            mouse::Interaction::default()
+        }
+    }
 }
 
 impl Overlay {
@@ -138,14 +459,357 @@ impl Overlay {
         caches: &mut Caches,
         shell: &mut Shell<'_, Message>,
     ) {
+        self.run_premove(state, messages, caches, shell);
+
+        if let canvas::Event::Mouse(mouse::Event::ButtonPressed(_)) = event {
+            if cursor.is_over(bounds) {
+                self.focused = true;
+            } else if self.focused {
+                self.lose_focus();
+                caches.board_overlay.clear();
+                caches.pieces.clear();
+                caches.overlay.clear();
+                shell.request_redraw();
+            }
+        }
+        if std::mem::take(&mut self.focus_changed) {
+            self.publish_focus(state, messages, shell);
+        }
+
         match event {
             canvas::Event::Mouse(mouse_event) => {
                 self.on_event_mouse(mouse_event, bounds, cursor, state, messages, caches, shell)
             }
+            canvas::Event::Keyboard(keyboard_event) => {
+                self.on_event_keyboard(keyboard_event, state, messages, caches, shell)
+            }
+            canvas::Event::Window(window::Event::RedrawRequested(now)) => {
+                self.on_redraw(*now, state, caches, shell)
+            }
             _ => return,
         }
     }
 
+    fn on_redraw<Message>(
+        &mut self,
+        now: Instant,
+        state: &BState,
+        caches: &mut Caches,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        let Some(animation) = &mut self.animation else {
+            return;
+        };
+        if animation.tick(now, state.animation, state.easing) {
+            self.animation = None;
+        } else {
+            shell.request_redraw();
+        }
+        caches.pieces.clear();
+    }
+
+    fn on_event_keyboard<Message>(
+        &mut self,
+        event: &keyboard::Event,
+        state: &BState,
+        messages: &Messages<Message>,
+        caches: &mut Caches,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        if let keyboard::Event::ModifiersChanged(modifiers) = event {
+            self.modifiers = *modifiers;
+            return;
+        }
+        if !state.keyboard || !self.focused {
+            return;
+        }
+        let keyboard::Event::KeyPressed {
+            key,
+            modifiers,
+            text,
+            ..
+        } = event
+        else {
+            return;
+        };
+        if modifiers.control() || modifiers.alt() || modifiers.logo() {
+            return;
+        }
+
+        if self.on_cursor_key(key, state, messages, caches, shell) {
+            shell.capture_event();
+            shell.request_redraw();
+            return;
+        }
+
+        let control = match key.as_ref() {
+            Key::Named(Named::ArrowLeft) => Some(Control::Previous),
+            Key::Named(Named::ArrowRight) => Some(Control::Next),
+            Key::Named(Named::ArrowUp | Named::Home) => Some(Control::First),
+            Key::Named(Named::ArrowDown | Named::End) => Some(Control::Last),
+            Key::Character(c) if self.flips(c, state, messages) => Some(Control::Flip),
+            _ => None,
+        };
+        if let Some(control) = control {
+            if let Some(on_control) = &messages.on_control {
+                shell.publish((on_control)(control));
+                shell.capture_event();
+            }
+            return;
+        }
+
+        if !Self::accepts_input(state, messages) {
+            return;
+        }
+
+        match key.as_ref() {
+            Key::Named(Named::Escape | Named::Backspace) if self.input.is_empty() => return,
+            Key::Named(Named::Escape) => self.input.clear(),
+            Key::Named(Named::Backspace) => {
+                self.input.pop();
+            }
+            Key::Named(Named::Enter) => {
+                let Some(mv) = self.input.exact(&state.game) else {
+                    return;
+                };
+                self.play_input(mv, messages, shell);
+            }
+            _ => {
+                let Some(c) = text.as_ref().and_then(|text| text.chars().next()) else {
+                    return;
+                };
+                if !self.input.push(c) {
+                    return;
+                }
+                if let Some(mv) = self.input.resolve(&state.game) {
+                    self.play_input(mv, messages, shell);
+                }
+            }
+        }
+
+        caches.overlay.clear();
+        shell.capture_event();
+        shell.request_redraw();
+    }
+
+    fn flips<Message>(&self, key: &str, state: &BState, messages: &Messages<Message>) -> bool {
+        let Some(flip) = state.flip_key else {
+            return false;
+        };
+        let mut chars = key.chars();
+        if chars.next() != Some(flip) || chars.next().is_some() || !self.input.is_empty() {
+            return false;
+        }
+
+        let mut input = MoveInput::default();
+        !(Self::accepts_input(state, messages)
+            && input.push(flip)
+            && !input.candidates(&state.game).is_empty())
+    }
+
+    fn on_cursor_key<Message>(
+        &mut self,
+        key: &Key,
+        state: &BState,
+        messages: &Messages<Message>,
+        caches: &mut Caches,
+        shell: &mut Shell<'_, Message>,
+    ) -> bool {
+        let cursor = match (key.as_ref(), self.cursor) {
+            (_, None) => return false,
+            (Key::Named(Named::ArrowLeft), Some(square)) => {
+                cursor::step(square, -1, 0, state.flipped)
+            }
+            (Key::Named(Named::ArrowRight), Some(square)) => {
+                cursor::step(square, 1, 0, state.flipped)
+            }
+            (Key::Named(Named::ArrowUp), Some(square)) => cursor::step(square, 0, 1, state.flipped),
+            (Key::Named(Named::ArrowDown), Some(square)) => {
+                cursor::step(square, 0, -1, state.flipped)
+            }
+            (Key::Named(Named::Enter | Named::Space), Some(square)) => {
+                self.activate(square, state, messages, shell);
+                square
+            }
+            (Key::Named(Named::Escape), Some(square))
+                if self.selected.is_some() || self.promotion.is_some() =>
+            {
+                self.clear_selection();
+                square
+            }
+            (Key::Named(Named::Escape), Some(_)) => {
+                self.cursor = None;
+                caches.board_overlay.clear();
+                self.publish_focus(state, messages, shell);
+                return true;
+            }
+            _ => return false,
+        };
+
+        self.cursor = Some(cursor);
+        caches.board_overlay.clear();
+        caches.pieces.clear();
+        caches.overlay.clear();
+        self.publish_focus(state, messages, shell);
+        true
+    }
+
+    fn activate<Message>(
+        &mut self,
+        square: Square,
+        state: &BState,
+        messages: &Messages<Message>,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        if let Some(mv) = self.promotion.take() {
+            let choice = promotion_choices_for(mv)
+                .into_iter()
+                .find(|(s, _)| *s == square);
+            self.clear_selection();
+            if let (Some((_, piece)), Some(on_move)) = (choice, &messages.on_move) {
+                let mv = ChessMove::new(mv.get_source(), mv.get_dest(), Some(piece));
+                shell.publish((on_move)(mv));
+            }
+            return;
+        }
+
+        if let Some(mv) = self.find_move(square) {
+            self.submit(mv, state, messages, shell);
+            return;
+        }
+
+        if self.selected == Some(square) {
+            self.clear_selection();
+        } else {
+            self.select(square, state, messages);
+        }
+    }
+
+    pub fn focus(&self, state: &BState) -> Option<Focus> {
+        let square = self.cursor?;
+        let pieces = self.premoves.pieces(&state.game.board);
+        let target = self.find_move(square).is_some()
+            || self.promotion_choices().iter().any(|(s, _)| *s == square);
+        Some(Focus::new(
+            &pieces,
+            square,
+            self.selected == Some(square),
+            target,
+        ))
+    }
+
+    fn publish_focus<Message>(
+        &self,
+        state: &BState,
+        messages: &Messages<Message>,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        if let Some(on_focus) = &messages.on_focus {
+            shell.publish((on_focus)(self.focus(state)));
+        }
+    }
+
+    fn play_input<Message>(
+        &mut self,
+        mv: ChessMove,
+        messages: &Messages<Message>,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        self.input.clear();
+        self.clear_selection();
+        if let Some(on_move) = &messages.on_move {
+            shell.publish((on_move)(mv));
+        }
+    }
+
+    pub fn on_event_editor<Message>(
+        &mut self,
+        event: &canvas::Event,
+        bounds: Rectangle,
+        cursor: mouse::Cursor,
+        state: &BState,
+        messages: &Messages<Message>,
+        caches: &mut Caches,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        let Some(setup) = state.setup else {
+            return;
+        };
+        let event = match event {
+            canvas::Event::Mouse(event) => event,
+            canvas::Event::Window(window::Event::RedrawRequested(now)) => {
+                return self.on_redraw(*now, state, caches, shell);
+            }
+            _ => return,
+        };
+        let board = editor::board_bounds(bounds);
+        let to_board = |pos: Point| {
+            let (col, row) = Self::pos_to_board(
+                board,
+                Point::new(pos.x - board.x, pos.y - board.y),
+                state.flipped,
+            );
+            Point::new(col, row)
+        };
+
+        match event {
+            mouse::Event::ButtonPressed(mouse::Button::Left) => {
+                let Some(pos) = cursor.position() else {
+                    return;
+                };
+                let square = Self::cursor_square(board, cursor, state.flipped);
+
+                if let Some(spare) = editor::spare_at(bounds, state.flipped, pos) {
+                    self.spare = Some(spare);
+                    self.selected = None;
+                } else if let Some(sq) = square
+                    && setup.piece(sq).is_some()
+                {
+                    self.spare = None;
+                    self.selected = Some(sq);
+                } else {
+                    return;
+                }
+                self.drag = Some(to_board(pos));
+
+                caches.board_overlay.clear();
+                caches.pieces.clear();
+                caches.drag.clear();
+                shell.capture_event();
+                shell.request_redraw();
+            }
+            mouse::Event::CursorMoved { position } if self.drag.is_some() => {
+                self.drag = Some(to_board(*position));
+                caches.board_overlay.clear();
+                caches.drag.clear();
+                shell.request_redraw();
+            }
+            mouse::Event::ButtonReleased(mouse::Button::Left) if self.drag.is_some() => {
+                let target = Self::cursor_square(board, cursor, state.flipped);
+                let mut edited = setup;
+                match (self.spare, self.selected, target) {
+                    (Some(spare), _, Some(to)) => edited.set(to, Some(spare)),
+                    (None, Some(from), Some(to)) => edited.relocate(from, to),
+                    (None, Some(from), None) => edited.set(from, None),
+                    _ => {}
+                }
+                self.clear_selection();
+
+                if edited != setup
+                    && let Some(on_edit) = &messages.on_edit
+                {
+                    shell.publish((on_edit)(edited));
+                }
+
+                caches.board_overlay.clear();
+                caches.pieces.clear();
+                caches.drag.clear();
+                shell.request_redraw();
+            }
+            _ => {}
+        }
+    }
+
     fn on_event_mouse<Message>(
         &mut self,
         event: &mouse::Event,
@@ -157,6 +821,16 @@ impl Overlay {
         shell: &mut Shell<'_, Message>,
     ) {
         match event {
+            mouse::Event::ButtonPressed(mouse::Button::Right) if !self.premoves.is_empty() => {
+                self.premoves.clear();
+                self.clear_selection();
+                self.publish_premoves(messages, shell);
+
+                caches.board_overlay.clear();
+                caches.pieces.clear();
+                caches.drag.clear();
+                shell.request_redraw();
+            }
             mouse::Event::ButtonPressed(mouse::Button::Right) => {
                 if let Some(sq) = Self::cursor_square(bounds, cursor, state.flipped) {
                     self.anchor = Some(sq);
@@ -167,66 +841,68 @@ impl Overlay {
                 }
             }
             mouse::Event::ButtonReleased(mouse::Button::Right) => {
-                let Some(from_h) = self.anchor else {
+                let Some(from_h) = self.anchor.take() else {
                     return;
                 };
 
                 if let Some(sq) = Self::cursor_square(bounds, cursor, state.flipped) {
-                    if sq == from_h {
-                        self.highlight ^= BitBoard::from_square(sq);
-                        caches.board_overlay.clear();
+                    let mark = if sq == from_h {
+                        Mark::Circle(sq)
                     } else {
-                        let arrow = ChessMove::new(from_h, sq, None);
-                        if let Some(pos) = self.arrows.iter().position(|mv| *mv == arrow) {
-                            self.arrows.remove(pos);
-                        } else {
-                            self.arrows.push(arrow);
+                        Mark::Arrow {
+                            from: from_h,
+                            to: sq,
                         }
-                        caches.overlay.clear();
-                    }
+                    };
+                    let brush = Brush::from_modifiers(self.modifiers);
+                    shape::toggle(&mut self.shapes, Shape::new(mark, brush));
+                    self.publish_shapes(messages, shell);
+
+                    caches.overlay.clear();
                     shell.request_redraw();
                 }
             }
+            mouse::Event::ButtonPressed(mouse::Button::Left) if self.promotion.is_some() => {
+                let choice = Self::cursor_square(bounds, cursor, state.flipped)
+                    .and_then(|sq| self.promotion_choices().into_iter().find(|(s, _)| *s == sq));
+
+                if let (Some(mv), Some((_, piece))) = (self.promotion.take(), choice) {
+                    let mv = ChessMove::new(mv.get_source(), mv.get_dest(), Some(piece));
+                    if let Some(on_move) = &messages.on_move {
+                        shell.publish((on_move)(mv));
+                    }
+                }
+
+                caches.board_overlay.clear();
+                caches.pieces.clear();
+                caches.overlay.clear();
+                shell.request_redraw();
+            }
             mouse::Event::ButtonPressed(mouse::Button::Left) => {
                 if let Some(pos) = cursor.position_in(bounds) {
                     let (col, row) = Self::pos_to_board(bounds, pos, state.flipped);
                     let pos = Point::new(col, row);
                     let square = Self::board_to_square(col, row);
 
-                    self.clear_overlay();
+                    if !self.shapes.is_empty() {
+                        self.clear_overlay();
+                        self.publish_shapes(messages, shell);
+                    }
                     caches.overlay.clear();
 
                     if let Some(mv) = self.find_move(square) {
-                        self.clear_selection();
                         caches.board_overlay.clear();
-                        if let Some(on_move) = &messages.on_move {
-                            shell.publish((on_move)(mv));
-                        }
+                        self.submit(mv, state, messages, shell);
                         return;
                     }
 
-                    if state.game.board.piece_on(square).is_none() {
-                        self.clear_selection();
+                    if !self.select(square, state, messages) {
                         caches.board_overlay.clear();
                         shell.request_redraw();
                         return;
                     }
-
-                    self.hints.clear();
-                    self.selected = Some(square);
                     self.drag = Some(pos);
 
-                    if messages.on_move.is_some() {
-                        for mv in MoveGen::new_legal(&state.game.board) {
-                            if mv.get_source() == square
-                                && (mv.get_promotion() == Some(Piece::Queen)
-                                    || mv.get_promotion().is_none())
-                            {
-                                self.hints.push(mv);
-                            }
-                        }
-                    }
-
                     caches.board_overlay.clear();
                     caches.pieces.clear();
                     caches.drag.clear();
@@ -237,10 +913,10 @@ impl Overlay {
                 self.drag = None;
                 if let Some(sq) = Self::cursor_square(bounds, cursor, state.flipped) {
                     if let Some(mv) = self.find_move(sq) {
-                        self.clear_selection();
-                        if let Some(on_move) = &messages.on_move {
-                            shell.publish((on_move)(mv));
+                        if Self::premove_color(state, messages).is_none() {
+                            self.dropped = Some(mv.get_dest());
                         }
+                        self.submit(mv, state, messages, shell);
                     }
                 }
                 caches.board_overlay.clear();
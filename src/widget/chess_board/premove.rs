@@ -0,0 +1,140 @@
+use chess::{
+    ALL_SQUARES, BitBoard, Board, ChessMove, Color, EMPTY, File, MoveGen, Piece, Square,
+    get_bishop_moves, get_king_moves, get_knight_moves, get_pawn_attacks, get_rook_moves,
+};
+
+pub type PieceMap = [Option<(Piece, Color)>; 64];
+
+pub fn follows(old: &Board, new: &Board) -> bool {
+    MoveGen::new_legal(old).any(|mv| old.make_move_new(mv) == *new)
+}
+
+pub fn legal(board: &Board, premove: ChessMove) -> Option<ChessMove> {
+    MoveGen::new_legal(board).find(|mv| {
+        mv.get_source() == premove.get_source()
+            && mv.get_dest() == premove.get_dest()
+            && mv.get_promotion() == premove.get_promotion()
+    })
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Premoves {
+    moves: Vec<ChessMove>,
+}
+
+impl Premoves {
+    pub fn moves(&self) -> &[ChessMove] {
+        &self.moves
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.moves.clear();
+    }
+
+    pub fn push(&mut self, mv: ChessMove, chain: bool) {
+        if !chain {
+            self.moves.clear();
+        }
+        self.moves.push(mv);
+    }
+
+    pub fn pop_front(&mut self) -> Option<ChessMove> {
+        if self.moves.is_empty() {
+            None
+        } else {
+            Some(self.moves.remove(0))
+        }
+    }
+
+    pub fn pieces(&self, board: &Board) -> PieceMap {
+        let mut pieces = [None; 64];
+        for square in ALL_SQUARES {
+            pieces[square.to_index()] = board.piece_on(square).zip(board.color_on(square));
+        }
+
+        for mv in &self.moves {
+            let source = mv.get_source();
+            let dest = mv.get_dest();
+            let Some((piece, color)) = pieces[source.to_index()].take() else {
+                continue;
+            };
+
+            let distance = source
+                .get_file()
+                .to_index()
+                .abs_diff(dest.get_file().to_index());
+            if piece == Piece::King && distance == 2 {
+                let (from, to) = if dest.get_file() == File::G {
+                    (File::H, File::F)
+                } else {
+                    (File::A, File::D)
+                };
+                let rank = source.get_rank();
+                let rook = pieces[Square::make_square(rank, from).to_index()].take();
+                pieces[Square::make_square(rank, to).to_index()] = rook;
+            }
+
+            pieces[dest.to_index()] = Some((mv.get_promotion().unwrap_or(piece), color));
+        }
+
+        pieces
+    }
+
+    pub fn dests(&self, board: &Board, square: Square) -> Vec<ChessMove> {
+        let pieces = self.pieces(board);
+        let Some((piece, color)) = pieces[square.to_index()] else {
+            return Vec::new();
+        };
+
+        let own = ALL_SQUARES
+            .iter()
+            .filter(|sq| matches!(pieces[sq.to_index()], Some((_, c)) if c == color))
+            .fold(EMPTY, |acc, &sq| acc | BitBoard::from_square(sq));
+
+        let targets = match piece {
+            Piece::Pawn => {
+                let mut targets = get_pawn_attacks(square, color, !EMPTY);
+                if let Some(one) = square.forward(color) {
+                    targets |= BitBoard::from_square(one);
+                    if square.get_rank() == color.to_second_rank()
+                        && let Some(two) = one.forward(color)
+                    {
+                        targets |= BitBoard::from_square(two);
+                    }
+                }
+                targets
+            }
+            Piece::Knight => get_knight_moves(square),
+            Piece::Bishop => get_bishop_moves(square, EMPTY),
+            Piece::Rook => get_rook_moves(square, EMPTY),
+            Piece::Queen => get_bishop_moves(square, EMPTY) | get_rook_moves(square, EMPTY),
+            Piece::King => {
+                let mut targets = get_king_moves(square);
+                let rank = color.to_my_backrank();
+                if square == Square::make_square(rank, File::E) {
+                    let rights = board.castle_rights(color);
+                    if rights.has_kingside() {
+                        targets |= BitBoard::from_square(Square::make_square(rank, File::G));
+                    }
+                    if rights.has_queenside() {
+                        targets |= BitBoard::from_square(Square::make_square(rank, File::C));
+                    }
+                }
+                targets
+            }
+        } & !own;
+
+        targets
+            .map(|dest| {
+                let promotion = (piece == Piece::Pawn
+                    && dest.get_rank() == color.to_their_backrank())
+                .then_some(Piece::Queen);
+                ChessMove::new(square, dest, promotion)
+            })
+            .collect()
+    }
+}
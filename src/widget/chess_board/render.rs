@@ -1,64 +1,40 @@
-use chess::{ALL_SQUARES, BitBoard, Color, File, Piece, Rank, Square};
+use chess::{Color, File, Piece, Rank, Square};
 use iced::{
-    Point, Rectangle, Size, Vector,
+    Font, Pixels, Point, Rectangle, Size, Vector,
     advanced::image,
-    widget::canvas::{self, Path, Stroke},
+    alignment,
+    widget::{
+        canvas::{self, Path, Stroke},
+        text,
+    },
 };
 
 use crate::{
-    assets::Assets,
     opiece::{
         self,
         font::{COL, ROW},
     },
+    piece_set::PieceSet,
     style::chess_board::Style,
 };
 
-use super::{BState, overlay::Overlay};
-
-pub struct Pieces {
-    white: [image::Handle; 6],
-    black: [image::Handle; 6],
-}
-
-impl Pieces {
-    fn new(base_path: &str) -> Self {
-        const PIECE_CHARS: [char; 6] = ['p', 'n', 'b', 'r', 'q', 'k'];
-
-        Self {
-            white: PIECE_CHARS.map(|c| {
-                let filename = format!("{base_path}/w{c}.png");
-                let data = Assets::get(&filename).unwrap();
-                image::Handle::from_bytes(data.data.into_owned())
-            }),
-            black: PIECE_CHARS.map(|c| {
-                let filename = format!("{base_path}/b{c}.png");
-                let data = Assets::get(&filename).unwrap();
-                image::Handle::from_bytes(data.data.into_owned())
-            }),
-        }
-    }
-
-    fn get(&self, piece: Piece, color: Color) -> &image::Handle {
-        let set = match color {
-            Color::White => &self.white[piece as usize],
-            Color::Black => &self.black[piece as usize],
-        };
-        set
-    }
-}
+use super::{
+    BState, editor,
+    overlay::Overlay,
+    premove::PieceMap,
+    shape::{self, Mark, Shape},
+};
 
 pub struct ChessBoardRenderer {
     tile_size: f32,
     tile: Size,
     style: Style,
     state: BState,
-    pieces: Pieces,
+    pieces: PieceSet,
 }
 
 impl ChessBoardRenderer {
-    pub fn new(style: Style, state: BState, bounds: Rectangle) -> Self {
-        let pieces = Pieces::new("pieces");
+    pub fn new(style: Style, state: BState, pieces: PieceSet, bounds: Rectangle) -> Self {
         let tile_size = bounds.width.min(bounds.height) / 8.0;
         let tile = Size::new(tile_size, tile_size);
         Self {
@@ -70,10 +46,13 @@ impl ChessBoardRenderer {
         }
     }
 
-    fn piece_image(&self, square: Square) -> Option<&image::Handle> {
-        let piece = self.state.game.board.piece_on(square)?;
-        let color = self.state.game.board.color_on(square)?;
-        Some(self.pieces.get(piece, color))
+    fn piece_image(&self, pieces: &PieceMap, square: Square) -> Option<image::Handle> {
+        let (piece, color) = pieces[square.to_index()]?;
+        Some(self.piece(piece, color))
+    }
+
+    fn piece(&self, piece: Piece, color: Color) -> image::Handle {
+        self.pieces.get(piece, color, self.tile_size)
     }
 
     // fn piece_piece(&self, square: Square) -> Option<(Piece, Color)> {
@@ -153,8 +132,17 @@ impl ChessBoardRenderer {
         }
     }
 
+    pub fn draw_palette(&self, frame: &mut canvas::Frame, bounds: Rectangle) {
+        for (slot, piece, color) in editor::palette(bounds, self.state.flipped) {
+            frame.draw_image(slot, image::Image::new(self.piece(piece, color)));
+        }
+    }
+
     pub fn draw_board_overlay(&self, frame: &mut canvas::Frame, overlay: &Overlay) {
-        if let Some(pos) = overlay.drag {
+        if let Some(pos) = overlay.drag
+            && (0.0..8.0).contains(&pos.x)
+            && (0.0..8.0).contains(&pos.y)
+        {
             let square = Square::make_square(
                 Rank::from_index(pos.y as usize),
                 File::from_index(pos.x as usize),
@@ -171,27 +159,23 @@ impl ChessBoardRenderer {
             );
         }
 
-        let mut highlights = BitBoard::new(0);
-        for square in ALL_SQUARES {
-            if BitBoard::from_square(square) & overlay.highlight != BitBoard::new(0) {
-                highlights |= BitBoard::from_square(square);
+        if let Some(ant) = self.state.game.annotation {
+            for sq in [ant.mv.get_source(), ant.mv.get_dest()] {
                 frame.fill_rectangle(
-                    self.square_position(&square),
+                    self.square_position(&sq),
                     self.tile,
-                    self.style.overlay.highlight,
+                    self.style.overlay.prev_move,
                 );
             }
         }
 
-        if let Some(ant) = self.state.game.annotation {
-            for sq in [ant.mv.get_source(), ant.mv.get_dest()] {
-                if BitBoard::from_square(sq) & highlights == BitBoard::new(0) {
-                    frame.fill_rectangle(
-                        self.square_position(&sq),
-                        self.tile,
-                        self.style.overlay.prev_move,
-                    );
-                }
+        for mv in overlay.premoves.moves() {
+            for sq in [mv.get_source(), mv.get_dest()] {
+                frame.fill_rectangle(
+                    self.square_position(&sq),
+                    self.tile,
+                    self.style.overlay.premove,
+                );
             }
         }
 
@@ -229,6 +213,20 @@ impl ChessBoardRenderer {
     }
 
     pub fn draw_pieces(&self, frame: &mut canvas::Frame, overlay: &Overlay) {
+        let pieces = overlay.pieces(&self.state);
+        let animation = overlay.animation.as_ref();
+
+        if let Some(animation) = animation {
+            for fade in &animation.fades {
+                let pos = Point::new(fade.at.x * self.tile_size, fade.at.y * self.tile_size);
+                frame.draw_image(
+                    Rectangle::new(pos, self.tile),
+                    image::Image::new(self.piece(fade.piece, fade.color))
+                        .opacity(1.0 - animation.progress),
+                );
+            }
+        }
+
         for row in 0..8 {
             let rank = Rank::from_index(row);
             for col in 0..8 {
@@ -238,6 +236,9 @@ impl ChessBoardRenderer {
                 if overlay.drag.is_some() && overlay.selected == Some(square) {
                     continue;
                 }
+                if animation.is_some_and(|animation| animation.is_sliding(square)) {
+                    continue;
+                }
 
                 let pos = self.tile_position(row, col);
 
@@ -248,107 +249,315 @@ impl ChessBoardRenderer {
                 //     }
                 // }
 
-                if let Some(img) = self.piece_image(square) {
-                    frame.draw_image(
-                        Rectangle::new(pos, self.tile),
-                        image::Image::new(img.clone()),
-                    );
+                if let Some(img) = self.piece_image(&pieces, square) {
+                    frame.draw_image(Rectangle::new(pos, self.tile), image::Image::new(img));
                 }
             }
         }
+
+        let Some(animation) = animation else {
+            return;
+        };
+
+        for slide in &animation.slides {
+            let at = slide.from + (slide.to - slide.from) * animation.progress;
+            let pos = Point::new(at.x * self.tile_size, at.y * self.tile_size);
+            frame.draw_image(
+                Rectangle::new(pos, self.tile),
+                image::Image::new(self.piece(slide.piece, slide.color)),
+            );
+        }
     }
 
     pub fn draw_drag(&self, frame: &mut canvas::Frame, overlay: &Overlay) {
-        if let (Some(square), Some(pos)) = (overlay.selected, overlay.drag) {
-            if let Some(img) = self.piece_image(square) {
-                let row = if self.state.flipped {
-                    pos.y
+        let Some(pos) = overlay.drag else {
+            return;
+        };
+        let pieces = overlay.pieces(&self.state);
+        let piece = overlay.spare.or_else(|| {
+            overlay
+                .selected
+                .and_then(|square| pieces[square.to_index()])
+        });
+
+        let Some((piece, color)) = piece else {
+            return;
+        };
+
+        let row = if self.state.flipped {
+            pos.y
+        } else {
+            8.0 - pos.y
+        };
+        let col = if self.state.flipped {
+            8.0 - pos.x
+        } else {
+            pos.x
+        };
+        let pos = Point::new(col * self.tile_size, row * self.tile_size);
+        let apos = pos - Vector::new(self.tile_size / 2.0, self.tile_size / 2.0);
+        frame.draw_image(
+            Rectangle::new(apos, self.tile),
+            image::Image::new(self.piece(piece, color)),
+        );
+    }
+
+    pub fn draw_shape_fills(&self, frame: &mut canvas::Frame, shapes: &[Shape]) {
+        for shape in shapes {
+            if let Mark::Fill(square) = shape.mark {
+                frame.fill_rectangle(
+                    self.square_position(&square),
+                    self.tile,
+                    shape.paint(&self.style.overlay.brushes),
+                );
+            }
+        }
+    }
+
+    pub fn draw_shapes(&self, frame: &mut canvas::Frame, shapes: &[Shape]) {
+        let mut order: Vec<_> = shapes.iter().zip(shape::arrow_stacks(shapes)).collect();
+        order.sort_by(|(a, _), (b, _)| b.weight.total_cmp(&a.weight));
+
+        for (shape, stack) in order {
+            match &shape.mark {
+                Mark::Arrow { from, to } => {
+                    let inset = stack.map_or(0.0, |(slot, count)| {
+                        0.28 * slot as f32 / count.saturating_sub(1).max(1) as f32
+                    });
+                    frame.fill(
+                        &self.arrow_path(*from, *to, shape.thickness(), inset),
+                        shape.paint(&self.style.overlay.brushes),
+                    );
+                }
+                Mark::Circle(square) => {
+                    let width = self.tile_size * 0.07 * shape.thickness();
+                    let center = self.square_position(square)
+                        + Vector::new(self.tile_size / 2.0, self.tile_size / 2.0);
+                    frame.stroke(
+                        &Path::circle(center, self.tile_size / 2.0 - width / 2.0),
+                        Stroke::default()
+                            .with_width(width)
+                            .with_color(shape.paint(&self.style.overlay.brushes)),
+                    );
+                }
+                Mark::Glyph(square, glyph) => {
+                    let radius = self.tile_size * 0.18;
+                    let center =
+                        self.square_position(square) + Vector::new(self.tile_size - radius, radius);
+                    frame.fill(
+                        &Path::circle(center, radius),
+                        shape.paint(&self.style.overlay.brushes),
+                    );
+                    frame.fill_text(canvas::Text {
+                        content: glyph.clone(),
+                        position: center,
+                        color: iced::Color {
+                            a: shape.opacity,
+                            ..iced::Color::WHITE
+                        },
+                        size: Pixels(radius * 1.3),
+                        font: Font::default(),
+                        align_x: text::Alignment::Center,
+                        align_y: alignment::Vertical::Center,
+                        ..canvas::Text::default()
+                    });
+                }
+                Mark::Fill(_) => {}
+            }
+        }
+
+        for (shape, stack) in shapes.iter().zip(shape::label_stacks(shapes)) {
+            if let (Mark::Arrow { to, .. }, Some(label), Some(stack)) =
+                (&shape.mark, &shape.label, stack)
+            {
+                self.draw_label(frame, shape, *to, label, stack);
+            }
+        }
+    }
+
+    fn draw_label(
+        &self,
+        frame: &mut canvas::Frame,
+        shape: &Shape,
+        square: Square,
+        label: &str,
+        (slot, count): (usize, usize),
+    ) {
+        let size = self.tile_size * 0.2;
+        let pad = size * 0.25;
+        let text = opiece::font::measure(label, size);
+        let badge = Size::new(text.width + pad * 2.0, text.height + pad * 2.0);
+
+        let center =
+            self.square_position(&square) + Vector::new(self.tile_size / 2.0, self.tile_size / 2.0);
+        let shift = (slot as f32 - (count - 1) as f32 / 2.0) * (badge.height + pad);
+        let top_left = Point::new(
+            center.x - badge.width / 2.0,
+            center.y - badge.height / 2.0 + shift,
+        );
+
+        let color = shape.brush.color(&self.style.overlay.brushes);
+        frame.fill(
+            &Path::rounded_rectangle(top_left, badge, (badge.height / 2.0).into()),
+            iced::Color {
+                a: shape.opacity,
+                ..color
+            },
+        );
+        opiece::font::draw_text(
+            frame,
+            label,
+            size,
+            top_left + Vector::new(pad, pad),
+            iced::Color {
+                a: shape.opacity,
+                ..iced::Color::WHITE
+            },
+        );
+    }
+
+    fn arrow_path(&self, from: Square, to: Square, thickness: f32, inset: f32) -> Path {
+        let spos = self.square_position(&from);
+        let dpos = self.square_position(&to);
+
+        let dx = ((dpos.x - spos.x) / self.tile_size).round() as i32;
+        let dy = ((dpos.y - spos.y) / self.tile_size).round() as i32;
+        let angle = (dy as f32).atan2(dx as f32);
+
+        let shaft = 0.11 * thickness;
+        let head = 0.26 * thickness;
+        let tail: &[(f32, f32)] = &[(0.36, shaft), (0.36, -shaft)];
+        let arrow: &[(f32, f32)] = &[
+            (-0.36 - inset, -shaft),
+            (-0.36 - inset, -head),
+            (-0.00 - inset, -0.00),
+            (-0.36 - inset, head),
+            (-0.36 - inset, shaft),
+        ];
+
+        let transform = |px: f32, py: f32, base: Point, angle: f32| -> Point {
+            let x = px * self.tile_size;
+            let y = py * self.tile_size;
+
+            let rx = x * angle.cos() - y * angle.sin();
+            let ry = x * angle.sin() + y * angle.cos();
+
+            Point::new(
+                base.x + rx + self.tile_size / 2.0,
+                base.y + ry + self.tile_size / 2.0,
+            )
+        };
+
+        let ddx = dx.abs();
+        let ddy = dy.abs();
+
+        Path::new(|p| {
+            if ddx.min(ddy) == 1 && ddx.max(ddy) == 2 {
+                let zy = if (dy * dx < 0) != (ddx > ddy) {
+                    shaft
                 } else {
-                    8.0 - pos.y
+                    -shaft
                 };
-                let col = if self.state.flipped {
-                    8.0 - pos.x
+
+                let (angl0, angl1) = if ddx > ddy {
+                    (0.0f32.atan2(dx as f32), (dy as f32).atan2(0.0))
                 } else {
-                    pos.x
+                    ((dy as f32).atan2(0.0), 0.0f32.atan2(dx as f32))
                 };
-                let pos = Point::new(col * self.tile_size, row * self.tile_size);
-                let apos = pos - Vector::new(self.tile_size / 2.0, self.tile_size / 2.0);
-                frame.draw_image(
-                    Rectangle::new(apos, self.tile),
-                    image::Image::new(img.clone()),
-                );
+
+                for &(px, py) in tail {
+                    p.line_to(transform(px, py, spos, angl0));
+                }
+                p.line_to(transform(2.00 + zy, -shaft, spos, angl0));
+                for &(px, py) in arrow {
+                    p.line_to(transform(px, py, dpos, angl1));
+                }
+                p.line_to(transform(2.00 - zy, shaft, spos, angl0));
+            } else {
+                for &(px, py) in tail {
+                    p.line_to(transform(px, py, spos, angle));
+                }
+                for &(px, py) in arrow {
+                    p.line_to(transform(px, py, dpos, angle));
+                }
             }
+            p.close();
+        })
+    }
+
+    pub fn draw_promotion(&self, frame: &mut canvas::Frame, overlay: &Overlay) {
+        let Some(mv) = overlay.promotion else {
+            return;
+        };
+        let Some(color) = self.state.game.board.color_on(mv.get_source()) else {
+            return;
+        };
+
+        let board = Size::new(self.tile_size * 8.0, self.tile_size * 8.0);
+        frame.fill_rectangle(Point::ORIGIN, board, self.style.overlay.promotion_backdrop);
+
+        let inset = self.tile_size * 0.1;
+        let size = Size::new(self.tile_size - inset * 2.0, self.tile_size - inset * 2.0);
+        for (square, piece) in overlay.promotion_choices() {
+            let pos = self.square_position(&square);
+            let center = pos + Vector::new(self.tile_size / 2.0, self.tile_size / 2.0);
+
+            frame.fill(
+                &Path::circle(center, self.tile_size / 2.0),
+                self.style.overlay.promotion,
+            );
+            frame.draw_image(
+                Rectangle::new(pos + Vector::new(inset, inset), size),
+                image::Image::new(self.piece(piece, color)),
+            );
         }
     }
 
-    pub fn draw_arrows(&self, frame: &mut canvas::Frame, overlay: &Overlay) {
-        for mv in overlay.arrows.iter() {
-            let spos = self.square_position(&mv.get_source());
-            let dpos = self.square_position(&mv.get_dest());
-
-            let dx = ((dpos.x - spos.x) / self.tile_size).round() as i32;
-            let dy = ((dpos.y - spos.y) / self.tile_size).round() as i32;
-            let angle = (dy as f32).atan2(dx as f32);
-
-            const TAIL: &[(f32, f32)] = &[(0.36, 0.11), (0.36, -0.11)];
-            const ARROW: &[(f32, f32)] = &[
-                (-0.36, -0.11),
-                (-0.36, -0.26),
-                (-0.00, -0.00),
-                (-0.36, 00.26),
-                (-0.36, 00.11),
-            ];
-
-            let transform = |px: f32, py: f32, base: Point, angle: f32| -> Point {
-                let x = px * self.tile_size;
-                let y = py * self.tile_size;
-
-                let rx = x * angle.cos() - y * angle.sin();
-                let ry = x * angle.sin() + y * angle.cos();
-
-                Point::new(
-                    base.x + rx + self.tile_size / 2.0,
-                    base.y + ry + self.tile_size / 2.0,
-                )
-            };
+    pub fn draw_focus(&self, frame: &mut canvas::Frame, overlay: &Overlay) {
+        let Some(square) = overlay.cursor else {
+            return;
+        };
 
-            let ddx = dx.abs();
-            let ddy = dy.abs();
-
-            let path = Path::new(|p| {
-                if ddx.min(ddy) == 1 && ddx.max(ddy) == 2 {
-                    let zy = if (dy * dx < 0) != (ddx > ddy) {
-                        0.11
-                    } else {
-                        -0.11
-                    };
-
-                    let (angl0, angl1) = if ddx > ddy {
-                        (0.0f32.atan2(dx as f32), (dy as f32).atan2(0.0))
-                    } else {
-                        ((dy as f32).atan2(0.0), 0.0f32.atan2(dx as f32))
-                    };
-
-                    for &(px, py) in TAIL {
-                        p.line_to(transform(px, py, spos, angl0));
-                    }
-                    p.line_to(transform(2.00 + zy, -0.11, spos, angl0));
-                    for &(px, py) in ARROW {
-                        p.line_to(transform(px, py, dpos, angl1));
-                    }
-                    p.line_to(transform(2.00 - zy, 00.11, spos, angl0));
-                } else {
-                    for &(px, py) in TAIL {
-                        p.line_to(transform(px, py, spos, angle));
-                    }
-                    for &(px, py) in ARROW {
-                        p.line_to(transform(px, py, dpos, angle));
-                    }
-                }
-                p.close();
-            });
+        let width = self.tile_size * 0.08;
+        let pos = self.square_position(&square) + Vector::new(width / 2.0, width / 2.0);
+        let size = Size::new(self.tile_size - width, self.tile_size - width);
+        frame.stroke(
+            &Path::rectangle(pos, size),
+            Stroke::default()
+                .with_width(width)
+                .with_color(self.style.overlay.focus),
+        );
+    }
 
-            frame.fill(&path, self.style.overlay.arrow);
+    pub fn draw_input(&self, frame: &mut canvas::Frame, overlay: &Overlay) {
+        if overlay.input.is_empty() {
+            return;
         }
+
+        let font = if overlay.input.candidates(&self.state.game).is_empty() {
+            self.style.overlay.input_error
+        } else {
+            self.style.overlay.input_font
+        };
+
+        let size = Size::new(self.tile_size * 2.5, self.tile_size * 0.6);
+        let pos = Point::new(
+            (self.tile_size * 8.0 - size.width) / 2.0,
+            self.tile_size * 8.0 - size.height - self.tile_size * 0.2,
+        );
+        frame.fill(
+            &Path::rounded_rectangle(pos, size, (size.height / 4.0).into()),
+            self.style.overlay.input,
+        );
+        frame.fill_text(canvas::Text {
+            content: overlay.input.text().to_string(),
+            position: pos + Vector::new(size.width / 2.0, size.height / 2.0),
+            color: font,
+            size: Pixels(size.height * 0.6),
+            font: Font::MONOSPACE,
+            align_x: text::Alignment::Center,
+            align_y: alignment::Vertical::Center,
+            ..canvas::Text::default()
+        });
     }
 }
@@ -0,0 +1,288 @@
+use std::str::FromStr;
+
+use chess::Square;
+use iced::{Color, keyboard::Modifiers};
+
+use crate::{
+    pgn,
+    style::chess_board::Brushes,
+    uci::{Info, Score},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Mark {
+    Arrow { from: Square, to: Square },
+    Fill(Square),
+    Circle(Square),
+    Glyph(Square, String),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Brush {
+    Green,
+    Red,
+    Blue,
+    Yellow,
+    Custom(Color),
+}
+
+impl Brush {
+    pub fn from_modifiers(modifiers: Modifiers) -> Self {
+        let red = modifiers.shift() || modifiers.control();
+        let blue = modifiers.alt() || modifiers.logo();
+        match (red, blue) {
+            (false, false) => Brush::Green,
+            (true, false) => Brush::Red,
+            (false, true) => Brush::Blue,
+            (true, true) => Brush::Yellow,
+        }
+    }
+
+    pub fn color(self, brushes: &Brushes) -> Color {
+        match self {
+            Brush::Green => brushes.green,
+            Brush::Red => brushes.red,
+            Brush::Blue => brushes.blue,
+            Brush::Yellow => brushes.yellow,
+            Brush::Custom(color) => color,
+        }
+    }
+}
+
+impl From<Color> for Brush {
+    fn from(color: Color) -> Self {
+        Brush::Custom(color)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Shape {
+    pub mark: Mark,
+    pub brush: Brush,
+    pub opacity: f32,
+    pub weight: f32,
+    pub label: Option<String>,
+}
+
+impl Shape {
+    pub fn new(mark: Mark, brush: impl Into<Brush>) -> Self {
+        Self {
+            mark,
+            brush: brush.into(),
+            opacity: 1.0,
+            weight: 1.0,
+            label: None,
+        }
+    }
+
+    pub fn arrow(from: Square, to: Square, brush: impl Into<Brush>) -> Self {
+        Self::new(Mark::Arrow { from, to }, brush)
+    }
+
+    pub fn fill(square: Square, brush: impl Into<Brush>) -> Self {
+        Self::new(Mark::Fill(square), brush)
+    }
+
+    pub fn circle(square: Square, brush: impl Into<Brush>) -> Self {
+        Self::new(Mark::Circle(square), brush)
+    }
+
+    pub fn glyph(square: Square, glyph: impl Into<String>, brush: impl Into<Brush>) -> Self {
+        Self::new(Mark::Glyph(square, glyph.into()), brush)
+    }
+
+    #[must_use]
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    #[must_use]
+    pub fn weight(mut self, weight: f32) -> Self {
+        self.weight = weight.clamp(0.0, 1.0);
+        self
+    }
+
+    #[must_use]
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn thickness(&self) -> f32 {
+        0.4 + 0.6 * self.weight
+    }
+
+    pub fn alpha(&self) -> f32 {
+        self.opacity * (0.3 + 0.7 * self.weight)
+    }
+
+    pub fn paint(&self, brushes: &Brushes) -> Color {
+        let color = self.brush.color(brushes);
+        Color {
+            a: color.a * self.alpha(),
+            ..color
+        }
+    }
+}
+
+pub fn toggle(shapes: &mut Vec<Shape>, shape: Shape) {
+    match shapes.iter().position(|s| s.mark == shape.mark) {
+        Some(index) if shapes[index].brush == shape.brush => {
+            shapes.remove(index);
+        }
+        Some(index) => shapes[index] = shape,
+        None => shapes.push(shape),
+    }
+}
+
+pub fn label_stacks(shapes: &[Shape]) -> Vec<Option<(usize, usize)>> {
+    stacks(shapes, |shape| match shape.mark {
+        Mark::Arrow { to, .. } if shape.label.is_some() => Some(to),
+        _ => None,
+    })
+}
+
+pub fn arrow_stacks(shapes: &[Shape]) -> Vec<Option<(usize, usize)>> {
+    stacks(shapes, |shape| match shape.mark {
+        Mark::Arrow { to, .. } => Some(to),
+        _ => None,
+    })
+}
+
+fn stacks(
+    shapes: &[Shape],
+    target: impl Fn(&Shape) -> Option<Square>,
+) -> Vec<Option<(usize, usize)>> {
+    shapes
+        .iter()
+        .enumerate()
+        .map(|(index, shape)| {
+            let to = target(shape)?;
+            let stack: Vec<_> = (0..shapes.len())
+                .filter(|&i| target(&shapes[i]) == Some(to))
+                .collect();
+            let slot = stack.iter().position(|&i| i == index)?;
+            Some((slot, stack.len()))
+        })
+        .collect()
+}
+
+pub fn score_label(score: Score) -> String {
+    match score {
+        Score::Cp(cp) => format!(
+            "{}{:.1}",
+            if cp < 0 { '-' } else { '+' },
+            cp.unsigned_abs() as f32 / 100.0
+        ),
+        Score::Mate(n) if n < 0 => format!("-#{}", n.unsigned_abs()),
+        Score::Mate(n) => format!("#{n}"),
+    }
+}
+
+pub fn multipv(lines: &[Info], brush: impl Into<Brush>) -> Vec<Shape> {
+    let brush = brush.into();
+    let mut lines: Vec<_> = lines.iter().filter(|info| !info.pv.is_empty()).collect();
+    lines.sort_by_key(|info| info.multipv.unwrap_or(1));
+
+    let count = lines.len();
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(rank, info)| {
+            let mv = info.pv[0];
+            let shape = Shape::arrow(mv.get_source(), mv.get_dest(), brush)
+                .weight(1.0 - rank as f32 / count as f32);
+            match info.score {
+                Some(score) => shape.label(score_label(score)),
+                None => shape,
+            }
+        })
+        .collect()
+}
+
+pub fn from_comment(comment: &str) -> Vec<Shape> {
+    let (_, commands) = pgn::split_commands(comment);
+    let mut shapes = Vec::new();
+
+    for (name, args) in commands {
+        for entry in args.split(',').map(str::trim) {
+            let mut chars = entry.chars();
+            let Some(brush) = chars.next().and_then(char_brush) else {
+                continue;
+            };
+            let squares = chars.as_str();
+            let mark = match (name.as_str(), squares.len()) {
+                ("csl", 2) => Square::from_str(squares).ok().map(Mark::Circle),
+                ("cal", 4) if squares.is_ascii() => Square::from_str(&squares[..2])
+                    .ok()
+                    .zip(Square::from_str(&squares[2..]).ok())
+                    .map(|(from, to)| Mark::Arrow { from, to }),
+                _ => None,
+            };
+            if let Some(mark) = mark {
+                shapes.push(Shape::new(mark, brush));
+            }
+        }
+    }
+    shapes
+}
+
+pub fn to_commands(shapes: &[Shape]) -> String {
+    let mut squares = Vec::new();
+    let mut arrows = Vec::new();
+    for shape in shapes {
+        let Some(c) = brush_char(shape.brush) else {
+            continue;
+        };
+        match shape.mark {
+            Mark::Fill(square) | Mark::Circle(square) => squares.push(format!("{c}{square}")),
+            Mark::Arrow { from, to } => arrows.push(format!("{c}{from}{to}")),
+            Mark::Glyph(..) => {}
+        }
+    }
+
+    let mut commands = String::new();
+    if !squares.is_empty() {
+        commands.push_str(&format!("[%csl {}]", squares.join(",")));
+    }
+    if !arrows.is_empty() {
+        commands.push_str(&format!("[%cal {}]", arrows.join(",")));
+    }
+    commands
+}
+
+pub fn write_comment(comment: &str, shapes: &[Shape]) -> String {
+    let (text, commands) = pgn::split_commands(comment);
+    let mut out: String = commands
+        .iter()
+        .filter(|(name, _)| name != "csl" && name != "cal")
+        .map(|(name, args)| format!("[%{name} {args}]"))
+        .collect();
+    out.push_str(&to_commands(shapes));
+    if !out.is_empty() && !text.is_empty() {
+        out.push(' ');
+    }
+    out.push_str(&text);
+    out
+}
+
+fn brush_char(brush: Brush) -> Option<char> {
+    match brush {
+        Brush::Green => Some('G'),
+        Brush::Red => Some('R'),
+        Brush::Blue => Some('B'),
+        Brush::Yellow => Some('Y'),
+        Brush::Custom(_) => None,
+    }
+}
+
+fn char_brush(c: char) -> Option<Brush> {
+    match c {
+        'G' => Some(Brush::Green),
+        'R' => Some(Brush::Red),
+        'B' => Some(Brush::Blue),
+        'Y' => Some(Brush::Yellow),
+        _ => None,
+    }
+}
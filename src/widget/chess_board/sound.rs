@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::fmt;
 use std::io::Cursor;
 use std::sync::{Arc, Mutex};
 
@@ -63,6 +64,12 @@ pub struct ChessBoardSound {
     sounds: Arc<Sounds>,
 }
 
+impl fmt::Debug for ChessBoardSound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChessBoardSound").finish_non_exhaustive()
+    }
+}
+
 impl ChessBoardSound {
     pub fn new() -> Self {
         let manager = AudioManager::new(Default::default()).unwrap();
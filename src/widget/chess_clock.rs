@@ -0,0 +1,243 @@
+use std::time::{Duration, Instant};
+
+use chess::Color;
+use iced::{
+    Element, Event, Font, Length, Pixels, Point, Rectangle, Renderer, Size,
+    advanced::{
+        Clipboard, Layout, Renderer as _, Shell, Widget,
+        graphics::geometry::Renderer as _,
+        layout::{Limits, Node},
+        renderer,
+        widget::{
+            Tree,
+            tree::{self, Tag},
+        },
+    },
+    alignment,
+    mouse::Cursor,
+    widget::{canvas, text},
+    window,
+};
+
+use crate::{
+    clock::{Clock, LOW_TIME},
+    style::chess_clock::{Catalog, Face, Style},
+};
+
+const FLASH_INTERVAL: Duration = Duration::from_millis(250);
+const FLASH_DURATION: Duration = Duration::from_secs(2);
+
+pub struct ChessClock<Theme: Catalog> {
+    width: Length,
+    height: Length,
+    class: Theme::Class<'static>,
+    remaining: Duration,
+    running: bool,
+    flagged: bool,
+    low_time: Duration,
+    tenths: Duration,
+}
+
+impl<Theme> ChessClock<Theme>
+where
+    Theme: Catalog,
+{
+    pub fn new(remaining: Duration) -> Self {
+        Self {
+            width: Length::Fixed(140.0),
+            height: Length::Fixed(44.0),
+            class: Theme::default(),
+            remaining,
+            running: false,
+            flagged: false,
+            low_time: LOW_TIME,
+            tenths: LOW_TIME,
+        }
+    }
+
+    pub fn from_clock(clock: &Clock, color: Color) -> Self {
+        Self::new(clock.remaining(color))
+            .running(clock.running() == Some(color))
+            .flagged(clock.flagged() == Some(color))
+    }
+
+    #[must_use]
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    #[must_use]
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    #[must_use]
+    pub fn running(mut self, running: bool) -> Self {
+        self.running = running;
+        self
+    }
+
+    #[must_use]
+    pub fn flagged(mut self, flagged: bool) -> Self {
+        self.flagged = flagged;
+        self
+    }
+
+    #[must_use]
+    pub fn low_time(mut self, threshold: Duration) -> Self {
+        self.low_time = threshold;
+        self
+    }
+
+    #[must_use]
+    pub fn tenths(mut self, threshold: Duration) -> Self {
+        self.tenths = threshold;
+        self
+    }
+
+    fn face(&self, style: &Style, flash: bool) -> Face {
+        if self.flagged {
+            if flash { style.idle } else { style.flagged }
+        } else if self.remaining <= self.low_time {
+            style.low_time
+        } else if self.running {
+            style.running
+        } else {
+            style.idle
+        }
+    }
+}
+
+pub fn format_time(remaining: Duration, tenths: Duration) -> String {
+    let secs = remaining.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+
+    if hours > 0 {
+        format!("{hours}:{minutes:02}:{seconds:02}")
+    } else if remaining < tenths {
+        format!("{minutes}:{seconds:02}.{}", remaining.subsec_millis() / 100)
+    } else {
+        format!("{minutes}:{seconds:02}")
+    }
+}
+
+impl<Message, Theme> Widget<Message, Theme, Renderer> for ChessClock<Theme>
+where
+    Theme: Catalog,
+{
+    fn tag(&self) -> Tag {
+        Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, self.height)
+    }
+
+    fn layout(&mut self, _tree: &mut Tree, _renderer: &Renderer, limits: &Limits) -> Node {
+        Node::new(limits.resolve(self.width, self.height, Size::ZERO))
+    }
+
+    fn update(
+        &mut self,
+        state: &mut Tree,
+        event: &Event,
+        _layout: Layout<'_>,
+        _cursor: Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) {
+        let Event::Window(window::Event::RedrawRequested(now)) = event else {
+            return;
+        };
+        let wstate: &mut State = state.state.downcast_mut();
+
+        if !self.flagged {
+            wstate.flagged_at = None;
+            wstate.flash = false;
+            return;
+        }
+
+        let elapsed = now.duration_since(*wstate.flagged_at.get_or_insert(*now));
+        if elapsed < FLASH_DURATION {
+            wstate.flash = (elapsed.as_millis() / FLASH_INTERVAL.as_millis()) % 2 == 1;
+            shell.request_redraw_at(*now + FLASH_INTERVAL);
+        } else {
+            wstate.flash = false;
+        }
+    }
+
+    fn draw(
+        &self,
+        state: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let wstate: &State = state.state.downcast_ref();
+        let style = theme.style(&self.class);
+        let face = self.face(&style, wstate.flash);
+
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let size = bounds.size();
+        let padding = size.height * 0.25;
+
+        frame.fill(
+            &canvas::Path::rounded_rectangle(Point::ORIGIN, size, style.radius.into()),
+            face.background,
+        );
+
+        if self.running && !self.flagged {
+            frame.fill(
+                &canvas::Path::circle(
+                    Point::new(padding + size.height * 0.1, size.height / 2.0),
+                    size.height * 0.1,
+                ),
+                style.indicator,
+            );
+        }
+
+        frame.fill_text(canvas::Text {
+            content: format_time(self.remaining, self.tenths),
+            position: Point::new(size.width - padding, size.height / 2.0),
+            color: face.font,
+            size: Pixels(size.height * 0.55),
+            font: Font::MONOSPACE,
+            align_x: text::Alignment::Right,
+            align_y: alignment::Vertical::Center,
+            ..canvas::Text::default()
+        });
+
+        let geometry = frame.into_geometry();
+        renderer.with_translation(bounds.position() - Point::ORIGIN, |renderer| {
+            renderer.draw_geometry(geometry);
+        });
+    }
+}
+
+#[derive(Default)]
+pub struct State {
+    flagged_at: Option<Instant>,
+    flash: bool,
+}
+
+impl<'a, Message, Theme> From<ChessClock<Theme>> for Element<'a, Message, Theme, Renderer>
+where
+    Theme: 'a + Catalog,
+    Message: 'a,
+{
+    fn from(clock: ChessClock<Theme>) -> Self {
+        Element::new(clock)
+    }
+}
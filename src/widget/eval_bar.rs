@@ -0,0 +1,261 @@
+use std::time::{Duration, Instant};
+
+use chess::Color;
+use iced::{
+    Element, Event, Font, Length, Pixels, Point, Rectangle, Renderer, Size,
+    advanced::{
+        Clipboard, Layout, Renderer as _, Shell, Widget,
+        graphics::geometry::Renderer as _,
+        layout::{Limits, Node},
+        renderer,
+        widget::{
+            Tree,
+            tree::{self, Tag},
+        },
+    },
+    alignment,
+    mouse::Cursor,
+    widget::{canvas, text},
+    window,
+};
+
+use crate::{style::eval_bar::Catalog, uci::Score};
+
+pub struct EvalBar<Theme: Catalog> {
+    width: Length,
+    height: Length,
+    class: Theme::Class<'static>,
+    score: Score,
+    side_to_move: Color,
+    flipped: bool,
+    duration: Duration,
+}
+
+impl<Theme> EvalBar<Theme>
+where
+    Theme: Catalog,
+{
+    pub fn new(score: Score) -> Self {
+        Self {
+            width: Length::Fixed(24.0),
+            height: Length::Fill,
+            class: Theme::default(),
+            score,
+            side_to_move: Color::White,
+            flipped: false,
+            duration: Duration::from_millis(300),
+        }
+    }
+
+    pub fn relative(score: Score, side_to_move: Color) -> Self {
+        Self::new(match (side_to_move, score) {
+            (Color::White, score) => score,
+            (Color::Black, Score::Cp(cp)) => Score::Cp(-cp),
+            (Color::Black, Score::Mate(n)) => Score::Mate(-n),
+        })
+        .side_to_move(side_to_move)
+    }
+
+    #[must_use]
+    pub fn side_to_move(mut self, side_to_move: Color) -> Self {
+        self.side_to_move = side_to_move;
+        self
+    }
+
+    #[must_use]
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    #[must_use]
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    #[must_use]
+    pub fn flipped(mut self, flipped: bool) -> Self {
+        self.flipped = flipped;
+        self
+    }
+
+    #[must_use]
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+}
+
+pub fn white_share(score: Score, side_to_move: Color) -> f32 {
+    match score {
+        Score::Cp(cp) => {
+            let chances = 2.0 / (1.0 + (-0.00368208 * cp as f32).exp()) - 1.0;
+            0.5 + 0.5 * chances
+        }
+        Score::Mate(0) if side_to_move == Color::Black => 1.0,
+        Score::Mate(n) if n > 0 => 1.0,
+        Score::Mate(_) => 0.0,
+    }
+}
+
+pub fn format_score(score: Score) -> String {
+    match score {
+        Score::Cp(cp) => format!("{:.1}", cp.unsigned_abs() as f32 / 100.0),
+        Score::Mate(n) => format!("M{}", n.unsigned_abs()),
+    }
+}
+
+fn ease_out(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+impl<Message, Theme> Widget<Message, Theme, Renderer> for EvalBar<Theme>
+where
+    Theme: Catalog,
+{
+    fn tag(&self) -> Tag {
+        Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, self.height)
+    }
+
+    fn layout(&mut self, _tree: &mut Tree, _renderer: &Renderer, limits: &Limits) -> Node {
+        Node::new(limits.resolve(self.width, self.height, Size::ZERO))
+    }
+
+    fn update(
+        &mut self,
+        state: &mut Tree,
+        event: &Event,
+        _layout: Layout<'_>,
+        _cursor: Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) {
+        let Event::Window(window::Event::RedrawRequested(now)) = event else {
+            return;
+        };
+        let wstate: &mut State = state.state.downcast_mut();
+        let target = white_share(self.score, self.side_to_move);
+
+        match wstate.target {
+            None => wstate.current = target,
+            Some(old) if old != target => {
+                wstate.from = wstate.current;
+                wstate.started = Some(*now);
+            }
+            _ => {}
+        }
+        wstate.target = Some(target);
+
+        let Some(started) = wstate.started else {
+            return;
+        };
+        let t = now.duration_since(started).as_secs_f32() / self.duration.as_secs_f32();
+        if t.is_nan() || t >= 1.0 {
+            wstate.current = target;
+            wstate.started = None;
+        } else {
+            wstate.current = wstate.from + (target - wstate.from) * ease_out(t);
+            shell.request_redraw();
+        }
+    }
+
+    fn draw(
+        &self,
+        state: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let wstate: &State = state.state.downcast_ref();
+        let style = theme.style(&self.class);
+
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let size = bounds.size();
+        let share = if wstate.target.is_some() {
+            wstate.current
+        } else {
+            white_share(self.score, self.side_to_move)
+        };
+        let white = size.height * share;
+
+        frame.fill(
+            &canvas::Path::rounded_rectangle(Point::ORIGIN, size, style.radius.into()),
+            style.black,
+        );
+        let top = if self.flipped {
+            0.0
+        } else {
+            size.height - white
+        };
+        frame.fill_rectangle(
+            Point::new(0.0, top),
+            Size::new(size.width, white),
+            style.white,
+        );
+        frame.fill_rectangle(
+            Point::new(0.0, size.height / 2.0 - 1.0),
+            Size::new(size.width, 2.0),
+            style.midline,
+        );
+
+        let white_winning = white_share(self.score, self.side_to_move) >= 0.5;
+        let padding = size.width * 0.2;
+        let (y, align_y) = if white_winning != self.flipped {
+            (size.height - padding, alignment::Vertical::Bottom)
+        } else {
+            (padding, alignment::Vertical::Top)
+        };
+        frame.fill_text(canvas::Text {
+            content: format_score(self.score),
+            position: Point::new(size.width / 2.0, y),
+            color: if white_winning {
+                style.white_font
+            } else {
+                style.black_font
+            },
+            size: Pixels(size.width * 0.4),
+            font: Font::MONOSPACE,
+            align_x: text::Alignment::Center,
+            align_y,
+            ..canvas::Text::default()
+        });
+
+        let geometry = frame.into_geometry();
+        renderer.with_translation(bounds.position() - Point::ORIGIN, |renderer| {
+            renderer.draw_geometry(geometry);
+        });
+    }
+}
+
+#[derive(Default)]
+pub struct State {
+    from: f32,
+    current: f32,
+    target: Option<f32>,
+    started: Option<Instant>,
+}
+
+impl<'a, Message, Theme> From<EvalBar<Theme>> for Element<'a, Message, Theme, Renderer>
+where
+    Theme: 'a + Catalog,
+    Message: 'a,
+{
+    fn from(bar: EvalBar<Theme>) -> Self {
+        Element::new(bar)
+    }
+}
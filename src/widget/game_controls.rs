@@ -0,0 +1,407 @@
+use iced::{
+    Color, Element, Event, Font, Length, Pixels, Point, Rectangle, Renderer, Size,
+    advanced::{
+        Clipboard, Layout, Renderer as _, Shell, Widget,
+        graphics::geometry::Renderer as _,
+        layout::{Limits, Node},
+        renderer,
+        widget::{
+            Tree,
+            tree::{self, Tag},
+        },
+    },
+    alignment,
+    mouse::{self, Cursor},
+    widget::{
+        canvas::{self, LineCap, Path, Stroke},
+        text,
+    },
+};
+
+use crate::{chess::GameTree, style::game_buttons::Catalog};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Control {
+    First,
+    Previous,
+    Next,
+    Last,
+    Flip,
+    Takeback,
+    OfferDraw,
+    Resign,
+}
+
+pub struct GameControls<Message, Theme: Catalog> {
+    width: Length,
+    height: Length,
+    spacing: f32,
+    class: Theme::Class<'static>,
+    back: bool,
+    forward: bool,
+    takeback: bool,
+    offer_draw: bool,
+    resign: bool,
+    on_control: Option<Box<dyn Fn(Control) -> Message>>,
+}
+
+impl<Message, Theme> GameControls<Message, Theme>
+where
+    Theme: Catalog,
+{
+    pub fn new() -> Self {
+        Self {
+            width: Length::Fill,
+            height: Length::Fixed(36.0),
+            spacing: 4.0,
+            class: Theme::default(),
+            back: false,
+            forward: false,
+            takeback: false,
+            offer_draw: false,
+            resign: false,
+            on_control: None,
+        }
+    }
+
+    pub fn from_tree(tree: &GameTree) -> Self {
+        Self::new()
+            .back(tree.can_go_back())
+            .forward(tree.can_go_forward())
+    }
+
+    #[must_use]
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    #[must_use]
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    #[must_use]
+    pub fn spacing(mut self, spacing: impl Into<Pixels>) -> Self {
+        self.spacing = spacing.into().0;
+        self
+    }
+
+    #[must_use]
+    pub fn back(mut self, back: bool) -> Self {
+        self.back = back;
+        self
+    }
+
+    #[must_use]
+    pub fn forward(mut self, forward: bool) -> Self {
+        self.forward = forward;
+        self
+    }
+
+    #[must_use]
+    pub fn takeback(mut self, takeback: bool) -> Self {
+        self.takeback = takeback;
+        self
+    }
+
+    #[must_use]
+    pub fn offer_draw(mut self, offer_draw: bool) -> Self {
+        self.offer_draw = offer_draw;
+        self
+    }
+
+    #[must_use]
+    pub fn resign(mut self, resign: bool) -> Self {
+        self.resign = resign;
+        self
+    }
+
+    #[must_use]
+    pub fn on_control<F>(mut self, on_control: F) -> Self
+    where
+        F: 'static + Fn(Control) -> Message,
+    {
+        self.on_control = Some(Box::new(on_control));
+        self
+    }
+
+    pub fn controls(&self) -> Vec<Control> {
+        let mut controls = vec![
+            Control::First,
+            Control::Previous,
+            Control::Next,
+            Control::Last,
+            Control::Flip,
+        ];
+        if self.takeback {
+            controls.push(Control::Takeback);
+        }
+        if self.offer_draw {
+            controls.push(Control::OfferDraw);
+        }
+        if self.resign {
+            controls.push(Control::Resign);
+        }
+        controls
+    }
+
+    pub fn is_enabled(&self, control: Control) -> bool {
+        self.on_control.is_some()
+            && match control {
+                Control::First | Control::Previous => self.back,
+                Control::Next | Control::Last => self.forward,
+                Control::Flip | Control::Takeback | Control::OfferDraw | Control::Resign => true,
+            }
+    }
+
+    fn buttons(&self, bounds: Rectangle) -> Vec<(Control, Rectangle)> {
+        let controls = self.controls();
+        let count = controls.len() as f32;
+        let width = (bounds.width - self.spacing * (count - 1.0)) / count;
+
+        controls
+            .into_iter()
+            .enumerate()
+            .map(|(index, control)| {
+                let x = bounds.x + index as f32 * (width + self.spacing);
+                let button =
+                    Rectangle::new(Point::new(x, bounds.y), Size::new(width, bounds.height));
+                (control, button)
+            })
+            .collect()
+    }
+
+    fn hovered(&self, bounds: Rectangle, cursor: Cursor) -> Option<Control> {
+        self.buttons(bounds)
+            .into_iter()
+            .find(|&(control, button)| self.is_enabled(control) && cursor.is_over(button))
+            .map(|(control, _)| control)
+    }
+}
+
+impl<Message, Theme> Default for GameControls<Message, Theme>
+where
+    Theme: Catalog,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Message, Theme> Widget<Message, Theme, Renderer> for GameControls<Message, Theme>
+where
+    Theme: Catalog,
+{
+    fn tag(&self) -> Tag {
+        Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, self.height)
+    }
+
+    fn layout(&mut self, _tree: &mut Tree, _renderer: &Renderer, limits: &Limits) -> Node {
+        Node::new(limits.resolve(self.width, self.height, Size::ZERO))
+    }
+
+    fn mouse_interaction(
+        &self,
+        _state: &Tree,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if self.hovered(layout.bounds(), cursor).is_some() {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+
+    fn update(
+        &mut self,
+        state: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+
+        match event {
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                let hovered = self.hovered(bounds, cursor);
+                let wstate: &mut State = state.state.downcast_mut();
+                if hovered != wstate.hovered {
+                    wstate.hovered = hovered;
+                    shell.request_redraw();
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                if let Some(on_control) = &self.on_control
+                    && let Some(control) = self.hovered(bounds, cursor)
+                {
+                    shell.publish(on_control(control));
+                    shell.capture_event();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(
+        &self,
+        _state: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let style = theme.style(&self.class);
+
+        let hovered = self.hovered(bounds, cursor);
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+
+        for (control, button) in self.buttons(Rectangle::new(Point::ORIGIN, bounds.size())) {
+            let face = if !self.is_enabled(control) {
+                style.disabled
+            } else if hovered == Some(control) {
+                style.hovered
+            } else {
+                style.button
+            };
+
+            frame.fill(
+                &Path::rounded_rectangle(button.position(), button.size(), style.radius.into()),
+                face.background,
+            );
+
+            let side = button.width.min(button.height) * 0.5;
+            let icon = Rectangle::new(
+                Point::new(
+                    button.center_x() - side / 2.0,
+                    button.center_y() - side / 2.0,
+                ),
+                Size::new(side, side),
+            );
+            draw_icon(&mut frame, control, icon, face.font);
+        }
+
+        let geometry = frame.into_geometry();
+        renderer.with_translation(bounds.position() - Point::ORIGIN, |renderer| {
+            renderer.draw_geometry(geometry);
+        });
+    }
+}
+
+fn draw_icon(frame: &mut canvas::Frame, control: Control, icon: Rectangle, color: Color) {
+    let at = |x: f32, y: f32| Point::new(icon.x + x * icon.width, icon.y + y * icon.height);
+    let polygon = |points: &[(f32, f32)]| {
+        Path::new(|p| {
+            for (index, &(x, y)) in points.iter().enumerate() {
+                if index == 0 {
+                    p.move_to(at(x, y));
+                } else {
+                    p.line_to(at(x, y));
+                }
+            }
+            p.close();
+        })
+    };
+    let stroke = Stroke::default()
+        .with_color(color)
+        .with_width(icon.width * 0.12)
+        .with_line_cap(LineCap::Round);
+
+    match control {
+        Control::First => {
+            frame.fill(&polygon(&[(0.9, 0.1), (0.9, 0.9), (0.3, 0.5)]), color);
+            frame.fill(
+                &polygon(&[(0.1, 0.1), (0.25, 0.1), (0.25, 0.9), (0.1, 0.9)]),
+                color,
+            );
+        }
+        Control::Previous => {
+            frame.fill(&polygon(&[(0.8, 0.1), (0.8, 0.9), (0.2, 0.5)]), color);
+        }
+        Control::Next => {
+            frame.fill(&polygon(&[(0.2, 0.1), (0.2, 0.9), (0.8, 0.5)]), color);
+        }
+        Control::Last => {
+            frame.fill(&polygon(&[(0.1, 0.1), (0.1, 0.9), (0.7, 0.5)]), color);
+            frame.fill(
+                &polygon(&[(0.75, 0.1), (0.9, 0.1), (0.9, 0.9), (0.75, 0.9)]),
+                color,
+            );
+        }
+        Control::Flip => {
+            frame.stroke(&Path::line(at(0.3, 0.9), at(0.3, 0.3)), stroke);
+            frame.fill(&polygon(&[(0.3, 0.05), (0.1, 0.35), (0.5, 0.35)]), color);
+            frame.stroke(&Path::line(at(0.7, 0.1), at(0.7, 0.7)), stroke);
+            frame.fill(&polygon(&[(0.7, 0.95), (0.5, 0.65), (0.9, 0.65)]), color);
+        }
+        Control::Takeback => {
+            let hook = Path::new(|p| {
+                p.move_to(at(0.85, 0.9));
+                p.line_to(at(0.85, 0.45));
+                p.line_to(at(0.35, 0.45));
+            });
+            frame.stroke(&hook, stroke);
+            frame.fill(&polygon(&[(0.05, 0.45), (0.35, 0.2), (0.35, 0.7)]), color);
+        }
+        Control::OfferDraw => {
+            frame.fill_text(canvas::Text {
+                content: "½".into(),
+                position: at(0.5, 0.5),
+                color,
+                size: Pixels(icon.height * 1.2),
+                font: Font::default(),
+                align_x: text::Alignment::Center,
+                align_y: alignment::Vertical::Center,
+                ..canvas::Text::default()
+            });
+        }
+        Control::Resign => {
+            frame.stroke(&Path::line(at(0.25, 0.05), at(0.25, 0.95)), stroke);
+            frame.fill(
+                &polygon(&[
+                    (0.25, 0.1),
+                    (0.85, 0.1),
+                    (0.7, 0.3),
+                    (0.85, 0.5),
+                    (0.25, 0.5),
+                ]),
+                color,
+            );
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct State {
+    hovered: Option<Control>,
+}
+
+impl<'a, Message, Theme> From<GameControls<Message, Theme>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Theme: 'a + Catalog,
+    Message: 'a,
+{
+    fn from(controls: GameControls<Message, Theme>) -> Self {
+        Element::new(controls)
+    }
+}
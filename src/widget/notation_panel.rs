@@ -0,0 +1,451 @@
+use iced::{
+    Border, Element, Event, Font, Length, Pixels, Point, Rectangle, Renderer, Size, Vector,
+    advanced::{
+        Clipboard, Layout, Renderer as _, Shell, Widget,
+        layout::{Limits, Node},
+        renderer::{self, Quad},
+        text::{self, LineHeight, Paragraph as _, Renderer as _, Shaping, Text, Wrapping},
+        widget::{
+            Tree,
+            tree::{self, Tag},
+        },
+    },
+    alignment,
+    mouse::{self, Cursor, ScrollDelta},
+};
+
+use crate::{
+    chess::{GameTree, NodeId},
+    notation::nag_glyph,
+    pgn::{self, GameResult, MovetextToken},
+    style::notation_panel::Catalog,
+};
+
+type Paragraph = <Renderer as text::Renderer>::Paragraph;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Token {
+    Number(String),
+    Move {
+        node: NodeId,
+        san: String,
+        depth: usize,
+    },
+    Comment(String),
+    Open,
+    Close,
+    Result(String),
+}
+
+impl Token {
+    pub fn text(&self) -> &str {
+        match self {
+            Token::Number(text) | Token::Comment(text) | Token::Result(text) => text,
+            Token::Move { san, .. } => san,
+            Token::Open => "(",
+            Token::Close => ")",
+        }
+    }
+
+    pub fn node(&self) -> Option<NodeId> {
+        match self {
+            Token::Move { node, .. } => Some(*node),
+            _ => None,
+        }
+    }
+}
+
+pub fn tokens(tree: &GameTree) -> Vec<Token> {
+    let mut tokens = Vec::new();
+
+    for token in pgn::movetext(tree) {
+        match token {
+            MovetextToken::Comment(comment) => {
+                let (text, _) = pgn::split_commands(comment);
+                tokens.extend(
+                    text.split_whitespace()
+                        .map(|word| Token::Comment(word.into())),
+                );
+            }
+            MovetextToken::Number(number) => tokens.push(Token::Number(number)),
+            MovetextToken::Move {
+                node,
+                mut san,
+                nags,
+                depth,
+            } => {
+                for &nag in nags {
+                    match nag_glyph(nag) {
+                        Some(glyph) if nag <= 6 => san.push_str(glyph),
+                        Some(glyph) => {
+                            san.push(' ');
+                            san.push_str(glyph);
+                        }
+                        None => san.push_str(&format!(" ${nag}")),
+                    }
+                }
+                tokens.push(Token::Move { node, san, depth });
+            }
+            MovetextToken::Open => tokens.push(Token::Open),
+            MovetextToken::Close => tokens.push(Token::Close),
+        }
+    }
+
+    let result = GameResult::from(tree.outcome());
+    if result != GameResult::Ongoing {
+        tokens.push(Token::Result(result.as_str().into()));
+    }
+
+    tokens
+}
+
+pub struct NotationPanel<Message, Theme: Catalog> {
+    width: Length,
+    height: Length,
+    class: Theme::Class<'static>,
+    tokens: Vec<Token>,
+    current: NodeId,
+    text_size: Pixels,
+    on_select: Option<Box<dyn Fn(NodeId) -> Message>>,
+}
+
+impl<Message, Theme> NotationPanel<Message, Theme>
+where
+    Theme: Catalog,
+{
+    pub fn new(tree: &GameTree) -> Self {
+        Self {
+            width: Length::Fill,
+            height: Length::Fill,
+            class: Theme::default(),
+            tokens: tokens(tree),
+            current: tree.cursor(),
+            text_size: Pixels(14.0),
+            on_select: None,
+        }
+    }
+
+    #[must_use]
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    #[must_use]
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    #[must_use]
+    pub fn text_size(mut self, size: impl Into<Pixels>) -> Self {
+        self.text_size = size.into();
+        self
+    }
+
+    #[must_use]
+    pub fn on_select<F>(self, on_select: F) -> Self
+    where
+        F: 'static + Fn(NodeId) -> Message,
+    {
+        self.on_select_maybe(Some(on_select))
+    }
+
+    #[must_use]
+    pub fn on_select_maybe<F>(mut self, on_select: Option<F>) -> Self
+    where
+        F: 'static + Fn(NodeId) -> Message,
+    {
+        self.on_select = on_select.map(|f| Box::new(f) as Box<dyn Fn(NodeId) -> Message>);
+        self
+    }
+
+    fn padding(&self) -> f32 {
+        self.text_size.0 * 0.6
+    }
+
+    fn gap(&self) -> f32 {
+        self.text_size.0 * 0.35
+    }
+
+    fn line_height(&self) -> f32 {
+        LineHeight::default().to_absolute(self.text_size).0
+    }
+
+    fn hit(&self, wstate: &State, bounds: Rectangle, cursor: Cursor) -> Option<usize> {
+        let position = cursor.position_in(bounds)? + Vector::new(0.0, wstate.offset);
+        wstate
+            .placed
+            .iter()
+            .zip(&self.tokens)
+            .position(|(placed, token)| {
+                token.node().is_some() && placed.expand(self.gap() / 2.0).contains(position)
+            })
+    }
+}
+
+impl<Message, Theme> Widget<Message, Theme, Renderer> for NotationPanel<Message, Theme>
+where
+    Theme: Catalog,
+{
+    fn tag(&self) -> Tag {
+        Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, self.height)
+    }
+
+    fn layout(&mut self, tree: &mut Tree, _renderer: &Renderer, limits: &Limits) -> Node {
+        let size = limits.resolve(self.width, self.height, Size::ZERO);
+        let wstate: &mut State = tree.state.downcast_mut();
+
+        let padding = self.padding();
+        let gap = self.gap();
+        let line_height = self.line_height();
+        let mut position = Point::new(padding, padding);
+
+        if wstate.tokens != self.tokens || wstate.text_size != self.text_size {
+            wstate.paragraphs = self
+                .tokens
+                .iter()
+                .map(|token| {
+                    Paragraph::with_text(Text {
+                        content: token.text(),
+                        bounds: Size::INFINITY,
+                        size: self.text_size,
+                        line_height: LineHeight::default(),
+                        font: Font::default(),
+                        align_x: text::Alignment::Left,
+                        align_y: alignment::Vertical::Top,
+                        shaping: Shaping::Advanced,
+                        wrapping: Wrapping::None,
+                    })
+                })
+                .collect();
+            wstate.tokens = self.tokens.clone();
+            wstate.text_size = self.text_size;
+        }
+
+        wstate.placed.clear();
+        for (index, (token, paragraph)) in self.tokens.iter().zip(&wstate.paragraphs).enumerate() {
+            let width = paragraph.min_width();
+
+            let glued = index == 0
+                || matches!(token, Token::Close)
+                || matches!(self.tokens[index - 1], Token::Open);
+            let mut x = position.x + if glued { 0.0 } else { gap };
+            if x + width > size.width - padding && position.x > padding {
+                x = padding;
+                position.y += line_height;
+            }
+
+            wstate.placed.push(Rectangle::new(
+                Point::new(x, position.y),
+                Size::new(width, line_height),
+            ));
+            position.x = x + width;
+        }
+        wstate.content_height = position.y + line_height + padding;
+
+        if wstate.current != Some(self.current) {
+            wstate.current = Some(self.current);
+
+            let placed = self
+                .tokens
+                .iter()
+                .position(|token| token.node() == Some(self.current))
+                .map(|index| wstate.placed[index]);
+            match placed {
+                Some(bounds) if bounds.y - padding < wstate.offset => {
+                    wstate.offset = bounds.y - padding;
+                }
+                Some(bounds)
+                    if bounds.y + bounds.height + padding > wstate.offset + size.height =>
+                {
+                    wstate.offset = bounds.y + bounds.height + padding - size.height;
+                }
+                Some(_) => {}
+                None => wstate.offset = 0.0,
+            }
+        }
+        wstate.offset = wstate
+            .offset
+            .clamp(0.0, (wstate.content_height - size.height).max(0.0));
+
+        Node::new(size)
+    }
+
+    fn mouse_interaction(
+        &self,
+        state: &Tree,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let wstate: &State = state.state.downcast_ref();
+
+        if self.on_select.is_some() && self.hit(wstate, layout.bounds(), cursor).is_some() {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+
+    fn update(
+        &mut self,
+        state: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let line_height = self.line_height();
+        let wstate: &mut State = state.state.downcast_mut();
+
+        match event {
+            Event::Mouse(mouse::Event::WheelScrolled { delta }) if cursor.is_over(bounds) => {
+                let dy = match delta {
+                    ScrollDelta::Lines { y, .. } => y * line_height,
+                    ScrollDelta::Pixels { y, .. } => *y,
+                };
+                let max = (wstate.content_height - bounds.height).max(0.0);
+                let offset = (wstate.offset - dy).clamp(0.0, max);
+
+                if offset != wstate.offset {
+                    wstate.offset = offset;
+                    wstate.hovered = self.hit(wstate, bounds, cursor);
+                    shell.capture_event();
+                    shell.request_redraw();
+                }
+            }
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                let hovered = self.hit(wstate, bounds, cursor);
+                if hovered != wstate.hovered {
+                    wstate.hovered = hovered;
+                    shell.request_redraw();
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                if let Some(on_select) = &self.on_select
+                    && let Some(node) = self
+                        .hit(wstate, bounds, cursor)
+                        .and_then(|index| self.tokens[index].node())
+                {
+                    shell.publish(on_select(node));
+                    shell.capture_event();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(
+        &self,
+        state: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor: Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let wstate: &State = state.state.downcast_ref();
+        let style = theme.style(&self.class);
+        let highlight = [0.0, self.gap() / 2.0];
+
+        renderer.fill_quad(
+            Quad {
+                bounds,
+                border: Border::default().rounded(style.radius),
+                ..Quad::default()
+            },
+            style.background,
+        );
+
+        renderer.with_layer(bounds, |renderer| {
+            let placed = wstate.placed.iter().zip(&wstate.paragraphs);
+            for (index, (token, (placed, paragraph))) in self.tokens.iter().zip(placed).enumerate()
+            {
+                let target = Rectangle {
+                    x: bounds.x + placed.x,
+                    y: bounds.y + placed.y - wstate.offset,
+                    ..*placed
+                };
+                if target.y + target.height < bounds.y || target.y > bounds.y + bounds.height {
+                    continue;
+                }
+
+                let color = match token {
+                    Token::Move { node, .. } if *node == self.current => {
+                        renderer.fill_quad(
+                            Quad {
+                                bounds: target.expand(highlight),
+                                border: Border::default().rounded(style.radius),
+                                ..Quad::default()
+                            },
+                            style.current,
+                        );
+                        style.current_font
+                    }
+                    Token::Move { depth, .. } => {
+                        if wstate.hovered == Some(index) {
+                            renderer.fill_quad(
+                                Quad {
+                                    bounds: target.expand(highlight),
+                                    border: Border::default().rounded(style.radius),
+                                    ..Quad::default()
+                                },
+                                style.hovered,
+                            );
+                        }
+                        if *depth == 0 {
+                            style.mainline
+                        } else {
+                            style.variation
+                        }
+                    }
+                    Token::Number(_) => style.number,
+                    Token::Comment(_) => style.comment,
+                    Token::Open | Token::Close => style.variation,
+                    Token::Result(_) => style.mainline,
+                };
+
+                renderer.fill_paragraph(paragraph, target.position(), color, bounds);
+            }
+        });
+    }
+}
+
+#[derive(Default)]
+pub struct State {
+    tokens: Vec<Token>,
+    text_size: Pixels,
+    paragraphs: Vec<Paragraph>,
+    placed: Vec<Rectangle>,
+    content_height: f32,
+    offset: f32,
+    current: Option<NodeId>,
+    hovered: Option<usize>,
+}
+
+impl<'a, Message, Theme> From<NotationPanel<Message, Theme>>
+    for Element<'a, Message, Theme, Renderer>
+where
+    Theme: 'a + Catalog,
+    Message: 'a,
+{
+    fn from(panel: NotationPanel<Message, Theme>) -> Self {
+        Element::new(panel)
+    }
+}
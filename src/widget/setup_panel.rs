@@ -0,0 +1,428 @@
+use chess::{CastleRights, Color, Square};
+use iced::{
+    Element, Event, Font, Length, Pixels, Point, Rectangle, Renderer, Size,
+    advanced::{
+        Clipboard, Layout, Renderer as _, Shell, Widget,
+        graphics::geometry::Renderer as _,
+        layout::{Limits, Node},
+        renderer,
+        widget::{
+            Tree,
+            tree::{self, Tag},
+        },
+    },
+    alignment,
+    mouse::{self, Cursor},
+    widget::{
+        canvas::{self, Path},
+        text,
+    },
+};
+
+use crate::{chess::GameState, setup::Setup, style::setup_panel::Catalog};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Action {
+    Side(Color),
+    Castling(Color, CastleRights),
+    EnPassant(Option<Square>),
+    Clear,
+    Start,
+    Mirror,
+    FlipColors,
+}
+
+impl Action {
+    pub fn apply(self, setup: &Setup) -> Setup {
+        let mut setup = *setup;
+        match self {
+            Action::Side(color) if setup.side != color => {
+                setup.side = color;
+                setup.en_passant = None;
+            }
+            Action::Side(_) => {}
+            Action::Castling(color, right) => setup.toggle_castling(color, right),
+            Action::EnPassant(square) => setup.en_passant = square,
+            Action::Clear => setup = Setup::empty(),
+            Action::Start => setup = Setup::default(),
+            Action::Mirror => setup = setup.mirror(),
+            Action::FlipColors => setup = setup.flip_colors(),
+        }
+        setup
+    }
+
+    pub fn is_active(self, setup: &Setup) -> bool {
+        match self {
+            Action::Side(color) => setup.side == color,
+            Action::Castling(color, CastleRights::KingSide) => {
+                setup.castling[color.to_index()].has_kingside()
+            }
+            Action::Castling(color, CastleRights::QueenSide) => {
+                setup.castling[color.to_index()].has_queenside()
+            }
+            Action::Castling(..) => false,
+            Action::EnPassant(square) => setup.en_passant == square,
+            Action::Clear | Action::Start | Action::Mirror | Action::FlipColors => false,
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            Action::Side(Color::White) => "White".into(),
+            Action::Side(Color::Black) => "Black".into(),
+            Action::Castling(_, CastleRights::KingSide) => "O-O".into(),
+            Action::Castling(..) => "O-O-O".into(),
+            Action::EnPassant(Some(square)) => square.to_string(),
+            Action::EnPassant(None) => "-".into(),
+            Action::Clear => "Clear".into(),
+            Action::Start => "Start".into(),
+            Action::Mirror => "Mirror".into(),
+            Action::FlipColors => "Flip colors".into(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Hit {
+    Action(Action),
+    Submit,
+}
+
+enum Item {
+    Label(String, Rectangle),
+    Button(Hit, Rectangle),
+    Status(Rectangle),
+}
+
+pub struct SetupPanel<Message, Theme: Catalog> {
+    width: Length,
+    height: Length,
+    row: f32,
+    spacing: f32,
+    padding: f32,
+    text_size: f32,
+    class: Theme::Class<'static>,
+    setup: Setup,
+    on_change: Option<Box<dyn Fn(Setup) -> Message>>,
+    on_submit: Option<Box<dyn Fn(GameState) -> Message>>,
+}
+
+impl<Message, Theme> SetupPanel<Message, Theme>
+where
+    Theme: Catalog,
+{
+    pub fn new(setup: Setup) -> Self {
+        Self {
+            width: Length::Fixed(260.0),
+            height: Length::Shrink,
+            row: 28.0,
+            spacing: 4.0,
+            padding: 8.0,
+            text_size: 14.0,
+            class: Theme::default(),
+            setup,
+            on_change: None,
+            on_submit: None,
+        }
+    }
+
+    #[must_use]
+    pub fn width(mut self, width: impl Into<Length>) -> Self {
+        self.width = width.into();
+        self
+    }
+
+    #[must_use]
+    pub fn height(mut self, height: impl Into<Length>) -> Self {
+        self.height = height.into();
+        self
+    }
+
+    #[must_use]
+    pub fn spacing(mut self, spacing: impl Into<Pixels>) -> Self {
+        self.spacing = spacing.into().0;
+        self
+    }
+
+    #[must_use]
+    pub fn text_size(mut self, text_size: impl Into<Pixels>) -> Self {
+        self.text_size = text_size.into().0;
+        self
+    }
+
+    #[must_use]
+    pub fn on_change<F>(mut self, on_change: F) -> Self
+    where
+        F: 'static + Fn(Setup) -> Message,
+    {
+        self.on_change = Some(Box::new(on_change));
+        self
+    }
+
+    #[must_use]
+    pub fn on_submit<F>(mut self, on_submit: F) -> Self
+    where
+        F: 'static + Fn(GameState) -> Message,
+    {
+        self.on_submit = Some(Box::new(on_submit));
+        self
+    }
+
+    fn rows(&self) -> usize {
+        if self.on_submit.is_some() { 11 } else { 10 }
+    }
+
+    fn items(&self, bounds: Rectangle) -> Vec<Item> {
+        let width = bounds.width - self.padding * 2.0;
+        let cells = |row: usize, count: usize| -> Vec<Rectangle> {
+            let y = bounds.y + self.padding + row as f32 * (self.row + self.spacing);
+            let cell = (width - self.spacing * (count as f32 - 1.0)) / count as f32;
+            (0..count)
+                .map(|index| {
+                    let x = bounds.x + self.padding + index as f32 * (cell + self.spacing);
+                    Rectangle::new(Point::new(x, y), Size::new(cell, self.row))
+                })
+                .collect()
+        };
+        let buttons = |row: usize, actions: &[Action]| -> Vec<Item> {
+            actions
+                .iter()
+                .zip(cells(row, actions.len()))
+                .map(|(&action, cell)| Item::Button(Hit::Action(action), cell))
+                .collect()
+        };
+
+        let mut items = vec![Item::Label("Side to move".into(), cells(0, 1)[0])];
+        items.extend(buttons(
+            1,
+            &[Action::Side(Color::White), Action::Side(Color::Black)],
+        ));
+
+        items.push(Item::Label("Castling".into(), cells(2, 1)[0]));
+        for (row, color) in [(3, Color::White), (4, Color::Black)] {
+            let row = cells(row, 3);
+            items.push(Item::Label(format!("{color:?}"), row[0]));
+            items.push(Item::Button(
+                Hit::Action(Action::Castling(color, CastleRights::KingSide)),
+                row[1],
+            ));
+            items.push(Item::Button(
+                Hit::Action(Action::Castling(color, CastleRights::QueenSide)),
+                row[2],
+            ));
+        }
+
+        items.push(Item::Label("En passant".into(), cells(5, 1)[0]));
+        let en_passant: Vec<Action> = std::iter::once(None)
+            .chain(self.setup.en_passant_squares().into_iter().map(Some))
+            .map(Action::EnPassant)
+            .collect();
+        items.extend(buttons(6, &en_passant));
+
+        items.push(Item::Status(cells(7, 1)[0]));
+        items.extend(buttons(8, &[Action::Clear, Action::Start]));
+        items.extend(buttons(9, &[Action::Mirror, Action::FlipColors]));
+        if self.on_submit.is_some() {
+            items.push(Item::Button(Hit::Submit, cells(10, 1)[0]));
+        }
+        items
+    }
+
+    fn is_enabled(&self, hit: Hit) -> bool {
+        match hit {
+            Hit::Action(_) => self.on_change.is_some(),
+            Hit::Submit => self.on_submit.is_some() && self.setup.game().is_ok(),
+        }
+    }
+
+    fn hovered(&self, bounds: Rectangle, cursor: Cursor) -> Option<Hit> {
+        self.items(bounds).into_iter().find_map(|item| match item {
+            Item::Button(hit, button) if self.is_enabled(hit) && cursor.is_over(button) => {
+                Some(hit)
+            }
+            _ => None,
+        })
+    }
+}
+
+impl<Message, Theme> Widget<Message, Theme, Renderer> for SetupPanel<Message, Theme>
+where
+    Theme: Catalog,
+{
+    fn tag(&self) -> Tag {
+        Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn size(&self) -> Size<Length> {
+        Size::new(self.width, self.height)
+    }
+
+    fn layout(&mut self, _tree: &mut Tree, _renderer: &Renderer, limits: &Limits) -> Node {
+        let rows = self.rows() as f32;
+        let height = self.padding * 2.0 + rows * self.row + (rows - 1.0) * self.spacing;
+        Node::new(limits.resolve(self.width, self.height, Size::new(0.0, height)))
+    }
+
+    fn mouse_interaction(
+        &self,
+        _state: &Tree,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if self.hovered(layout.bounds(), cursor).is_some() {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::default()
+        }
+    }
+
+    fn update(
+        &mut self,
+        state: &mut Tree,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+
+        match event {
+            Event::Mouse(mouse::Event::CursorMoved { .. }) => {
+                let hovered = self.hovered(bounds, cursor);
+                let wstate: &mut State = state.state.downcast_mut();
+                if hovered != wstate.hovered {
+                    wstate.hovered = hovered;
+                    shell.request_redraw();
+                }
+            }
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left)) => {
+                match self.hovered(bounds, cursor) {
+                    Some(Hit::Action(action)) => {
+                        if let Some(on_change) = &self.on_change {
+                            shell.publish(on_change(action.apply(&self.setup)));
+                        }
+                    }
+                    Some(Hit::Submit) => {
+                        if let Some(on_submit) = &self.on_submit
+                            && let Ok(game) = self.setup.game()
+                        {
+                            shell.publish(on_submit(game));
+                        }
+                    }
+                    None => return,
+                }
+                shell.capture_event();
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(
+        &self,
+        _state: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let bounds = layout.bounds();
+        let style = theme.style(&self.class);
+
+        let hovered = self.hovered(bounds, cursor);
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let local = Rectangle::with_size(bounds.size());
+
+        frame.fill(
+            &Path::rounded_rectangle(Point::ORIGIN, bounds.size(), style.radius.into()),
+            style.background,
+        );
+
+        let label = |frame: &mut canvas::Frame,
+                     content: String,
+                     cell: Rectangle,
+                     color: iced::Color,
+                     center: bool| {
+            let (x, align_x) = if center {
+                (cell.center_x(), text::Alignment::Center)
+            } else {
+                (cell.x, text::Alignment::Left)
+            };
+            frame.fill_text(canvas::Text {
+                content,
+                position: Point::new(x, cell.center_y()),
+                color,
+                size: Pixels(self.text_size),
+                font: Font::default(),
+                align_x,
+                align_y: alignment::Vertical::Center,
+                ..canvas::Text::default()
+            });
+        };
+
+        for item in self.items(local) {
+            match item {
+                Item::Label(content, cell) => label(&mut frame, content, cell, style.label, false),
+                Item::Status(cell) => {
+                    let (content, color) = match self.setup.game() {
+                        Ok(_) => ("Valid position".to_string(), style.valid),
+                        Err(err) => (err.to_string(), style.invalid),
+                    };
+                    label(&mut frame, content, cell, color, false);
+                }
+                Item::Button(hit, button) => {
+                    let face = match hit {
+                        _ if !self.is_enabled(hit) => style.disabled,
+                        Hit::Action(action) if action.is_active(&self.setup) => style.active,
+                        _ if hovered == Some(hit) => style.hovered,
+                        _ => style.button,
+                    };
+                    frame.fill(
+                        &Path::rounded_rectangle(
+                            button.position(),
+                            button.size(),
+                            style.radius.into(),
+                        ),
+                        face.background,
+                    );
+
+                    let content = match hit {
+                        Hit::Action(action) => action.label(),
+                        Hit::Submit => "Done".into(),
+                    };
+                    label(&mut frame, content, button, face.font, true);
+                }
+            }
+        }
+
+        let geometry = frame.into_geometry();
+        renderer.with_translation(bounds.position() - Point::ORIGIN, |renderer| {
+            renderer.draw_geometry(geometry);
+        });
+    }
+}
+
+#[derive(Default)]
+pub struct State {
+    hovered: Option<Hit>,
+}
+
+impl<'a, Message, Theme> From<SetupPanel<Message, Theme>> for Element<'a, Message, Theme, Renderer>
+where
+    Theme: 'a + Catalog,
+    Message: 'a,
+{
+    fn from(panel: SetupPanel<Message, Theme>) -> Self {
+        Element::new(panel)
+    }
+}
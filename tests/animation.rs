@@ -0,0 +1,118 @@
+mod common;
+
+use chess::{ChessMove, Piece, Square};
+use iced::Point;
+use iced_chess::{
+    chess::GameState,
+    widget::chess_board::animation::{Animation, Easing, position},
+};
+
+use common::{mv, pieces, state};
+
+fn play(game: &GameState, source: Square, dest: Square) -> GameState {
+    game.make_move(mv(source, dest))
+}
+
+#[test]
+fn easing_starts_and_ends_in_place() {
+    for easing in [
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+    ] {
+        assert_eq!(easing.apply(0.0), 0.0);
+        assert_eq!(easing.apply(1.0), 1.0);
+        assert_eq!(easing.apply(2.0), 1.0);
+    }
+    assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+    assert!(Easing::EaseIn.apply(0.25) < 0.25);
+    assert!(Easing::EaseOut.apply(0.25) > 0.25);
+}
+
+#[test]
+fn castling_slides_king_and_rook() {
+    let old = state("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+    let new = play(&old, Square::E1, Square::G1);
+
+    let animation = Animation::plan(&pieces(&old), &pieces(&new), false, false, None).unwrap();
+
+    assert_eq!(animation.slides.len(), 2);
+    assert!(animation.is_sliding(Square::G1));
+    assert!(animation.is_sliding(Square::F1));
+    assert!(animation.fades.is_empty());
+
+    let king = animation
+        .slides
+        .iter()
+        .find(|slide| slide.piece == Piece::King)
+        .unwrap();
+    assert_eq!(king.from, position(Square::E1, false));
+    assert_eq!(king.to, position(Square::G1, false));
+}
+
+#[test]
+fn captured_piece_fades() {
+    let old = play(&GameState::default(), Square::E2, Square::E4);
+    let old = play(&old, Square::D7, Square::D5);
+    let new = play(&old, Square::E4, Square::D5);
+
+    let animation = Animation::plan(&pieces(&old), &pieces(&new), false, false, None).unwrap();
+
+    assert_eq!(animation.slides.len(), 1);
+    assert!(animation.is_sliding(Square::D5));
+    assert_eq!(animation.fades.len(), 1);
+    assert_eq!(animation.fades[0].piece, Piece::Pawn);
+    assert_eq!(animation.fades[0].at, position(Square::D5, false));
+}
+
+#[test]
+fn promotion_slides_from_pawn() {
+    let old = state("8/4P3/8/8/8/8/k7/4K3 w - - 0 1");
+    let new = old.make_move(ChessMove::new(Square::E7, Square::E8, Some(Piece::Queen)));
+
+    let animation = Animation::plan(&pieces(&old), &pieces(&new), false, false, None).unwrap();
+
+    assert_eq!(animation.slides.len(), 1);
+    assert_eq!(animation.slides[0].piece, Piece::Queen);
+    assert_eq!(animation.slides[0].from, position(Square::E7, false));
+    assert!(animation.fades.is_empty());
+}
+
+#[test]
+fn undo_slides_back() {
+    let old = play(&GameState::default(), Square::G1, Square::F3);
+    let new = GameState::default();
+
+    let animation = Animation::plan(&pieces(&old), &pieces(&new), false, false, None).unwrap();
+
+    assert_eq!(animation.slides.len(), 1);
+    assert_eq!(animation.slides[0].square, Square::G1);
+    assert_eq!(animation.slides[0].from, position(Square::F3, false));
+}
+
+#[test]
+fn flip_slides_every_piece() {
+    let game = GameState::default();
+
+    let animation = Animation::plan(&pieces(&game), &pieces(&game), false, true, None).unwrap();
+
+    assert_eq!(animation.slides.len(), 32);
+    assert_eq!(position(Square::A1, false), Point::new(0.0, 7.0));
+    assert_eq!(position(Square::A1, true), Point::new(7.0, 0.0));
+}
+
+#[test]
+fn dropped_piece_does_not_slide() {
+    let old = GameState::default();
+    let new = play(&old, Square::E2, Square::E4);
+
+    assert_eq!(
+        Animation::plan(&pieces(&old), &pieces(&new), false, false, Some(Square::E4)),
+        None
+    );
+    assert_eq!(
+        Animation::plan(&pieces(&old), &pieces(&old), false, false, None),
+        None
+    );
+}
@@ -0,0 +1,44 @@
+use chess::{Color, Piece};
+use iced_chess::{
+    chess::{GameState, GameTree},
+    widget::captured_pieces::Material,
+};
+
+fn play(tree: &mut GameTree, moves: &[&str]) {
+    for san in moves {
+        let mv = tree.current().parse_san(san).unwrap();
+        tree.play(mv);
+    }
+}
+
+#[test]
+fn captures_from_history() {
+    let mut tree = GameTree::default();
+    play(
+        &mut tree,
+        &["e4", "d5", "exd5", "Qxd5", "Nc3", "Qxa2", "Rxa2"],
+    );
+
+    let material = Material::from_tree(&tree, tree.cursor());
+    assert_eq!(material.captured(Color::White), [2, 0, 0, 0, 0, 0]);
+    assert_eq!(material.captured(Color::Black), [1, 0, 0, 0, 1, 0]);
+    assert_eq!(material.advantage(Color::White), 8);
+    assert_eq!(material.advantage(Color::Black), -8);
+
+    let start = Material::from_tree(&tree, GameTree::ROOT);
+    assert_eq!(start, Material::from_state(&GameState::default()));
+    assert_eq!(start.advantage(Color::White), 0);
+}
+
+#[test]
+fn promotions_are_not_captures() {
+    let state =
+        GameState::from_fen("rnbqkbnr/pppppppp/8/8/8/8/1PPPPPPP/RNBQKBNQ w - - 0 1").unwrap();
+    let material = Material::from_state(&state);
+
+    let mut lost = [0; 6];
+    lost[Piece::Rook.to_index()] = 1;
+    assert_eq!(material.captured(Color::White), lost);
+    assert_eq!(material.captured(Color::Black), [0; 6]);
+    assert_eq!(material.advantage(Color::White), 3);
+}
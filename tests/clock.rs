@@ -0,0 +1,190 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chess::Color;
+use iced_chess::clock::{Clock, ClockEvent, Increment, ManualTime, Stage, TimeControl, TimeSource};
+
+fn secs(secs: u64) -> Duration {
+    Duration::from_secs(secs)
+}
+
+fn clock(control: TimeControl) -> (Clock, ManualTime) {
+    let time = ManualTime::new();
+    let clock = Clock::new(control).source(time.clone());
+    (clock, time)
+}
+
+#[test]
+fn sudden_death_flags() {
+    let (mut clock, time) = clock(TimeControl::sudden_death(secs(60)));
+    clock.start(Color::White);
+
+    time.advance(secs(20));
+    assert_eq!(clock.remaining(Color::White), secs(40));
+    assert_eq!(clock.press(), None);
+    assert_eq!(clock.running(), Some(Color::Black));
+
+    time.advance(secs(5));
+    clock.press();
+    time.advance(secs(40));
+    assert_eq!(clock.tick(), vec![ClockEvent::Flag(Color::White)]);
+    assert_eq!(clock.flagged(), Some(Color::White));
+    assert_eq!(clock.remaining(Color::White), Duration::ZERO);
+    assert_eq!(clock.remaining(Color::Black), secs(55));
+    assert!(!clock.is_running());
+}
+
+#[test]
+fn increments_and_delays() {
+    let (mut fischer, time) = clock(TimeControl::fischer(secs(60), secs(5)));
+    fischer.start(Color::White);
+    time.advance(secs(2));
+    fischer.press();
+    assert_eq!(fischer.remaining(Color::White), secs(63));
+
+    let (mut bronstein, time) = clock(TimeControl::bronstein(secs(60), secs(5)));
+    bronstein.start(Color::White);
+    time.advance(secs(2));
+    bronstein.press();
+    assert_eq!(bronstein.remaining(Color::White), secs(60));
+    time.advance(secs(8));
+    bronstein.press();
+    assert_eq!(bronstein.remaining(Color::Black), secs(57));
+
+    let (mut delay, time) = clock(TimeControl::delay(secs(60), secs(5)));
+    delay.start(Color::White);
+    time.advance(secs(3));
+    assert_eq!(delay.remaining(Color::White), secs(60));
+    time.advance(secs(4));
+    assert_eq!(delay.remaining(Color::White), secs(58));
+    delay.press();
+    assert_eq!(delay.remaining(Color::White), secs(58));
+}
+
+#[test]
+fn multi_stage() {
+    let increment = Increment::Fischer(secs(30));
+    let control = TimeControl::from_stages(vec![
+        Stage::new(secs(90 * 60), increment).moves(40),
+        Stage::new(secs(30 * 60), increment),
+    ])
+    .unwrap();
+    let (mut clock, time) = clock(control);
+
+    clock.start(Color::White);
+    for _ in 0..40 {
+        time.advance(secs(60));
+        clock.press();
+        time.advance(secs(30));
+        clock.press();
+    }
+
+    assert_eq!(clock.moves(Color::White), 40);
+    assert_eq!(clock.stage(Color::White), 1);
+    assert_eq!(
+        clock.remaining(Color::White),
+        secs(90 * 60 + 30 * 60 - 40 * 30)
+    );
+    assert_eq!(clock.stage(Color::Black), 1);
+    assert_eq!(clock.remaining(Color::Black), secs(90 * 60 + 30 * 60));
+    assert_eq!(TimeControl::from_stages(Vec::new()), None);
+}
+
+struct SteppingTime {
+    now: Mutex<Instant>,
+    step: Duration,
+}
+
+impl TimeSource for SteppingTime {
+    fn now(&self) -> Instant {
+        let mut now = self.now.lock().unwrap();
+        let current = *now;
+        *now += self.step;
+        current
+    }
+}
+
+#[test]
+fn press_reads_the_time_source_once() {
+    let source = |step| SteppingTime {
+        now: Mutex::new(Instant::now()),
+        step: secs(step),
+    };
+
+    let mut clock = Clock::new(TimeControl::sudden_death(secs(60))).source(source(40));
+    clock.start(Color::White);
+    assert_eq!(clock.press(), None);
+    assert_eq!(clock.remaining(Color::White), secs(20));
+
+    let mut clock = Clock::new(TimeControl::fischer(secs(60), secs(5))).source(source(60));
+    clock.start(Color::White);
+    assert_eq!(clock.press(), Some(ClockEvent::Flag(Color::White)));
+}
+
+#[test]
+fn time_odds() {
+    let time = ManualTime::new();
+    let mut clock = Clock::with_odds(
+        TimeControl::sudden_death(secs(300)),
+        TimeControl::sudden_death(secs(60)),
+    )
+    .source(time.clone());
+
+    assert_eq!(clock.remaining(Color::White), secs(300));
+    assert_eq!(clock.remaining(Color::Black), secs(60));
+
+    clock.start(Color::Black);
+    time.advance(secs(61));
+    assert_eq!(clock.press(), Some(ClockEvent::Flag(Color::Black)));
+}
+
+#[test]
+fn low_time_once_per_period() {
+    let control = TimeControl::from_stages(vec![
+        Stage::new(secs(20), Increment::None).moves(1),
+        Stage::new(secs(20), Increment::None),
+    ])
+    .unwrap();
+    let (mut clock, time) = clock(control);
+
+    clock.start(Color::White);
+    time.advance(secs(5));
+    assert!(clock.tick().is_empty());
+    time.advance(secs(6));
+    assert_eq!(clock.tick(), vec![ClockEvent::LowTime(Color::White)]);
+    time.advance(secs(1));
+    assert!(clock.tick().is_empty());
+
+    clock.press();
+    clock.press();
+    time.advance(secs(20));
+    assert_eq!(clock.tick(), vec![ClockEvent::LowTime(Color::White)]);
+}
+
+#[test]
+fn pause_and_resume() {
+    let (mut clock, time) = clock(TimeControl::sudden_death(secs(60)));
+    clock.start(Color::White);
+    time.advance(secs(10));
+    clock.pause();
+    time.advance(secs(100));
+    assert_eq!(clock.remaining(Color::White), secs(50));
+    assert!(clock.tick().is_empty());
+
+    clock.resume();
+    time.advance(secs(10));
+    assert_eq!(clock.remaining(Color::White), secs(40));
+}
+
+#[test]
+fn display_format() {
+    use iced_chess::widget::chess_clock::format_time;
+
+    assert_eq!(format_time(secs(5400), secs(10)), "1:30:00");
+    assert_eq!(format_time(secs(185), secs(10)), "3:05");
+    assert_eq!(
+        format_time(Duration::from_millis(9_470), secs(10)),
+        "0:09.4"
+    );
+    assert_eq!(format_time(Duration::from_millis(9_470), secs(5)), "0:09");
+}
@@ -0,0 +1,19 @@
+#![allow(dead_code)]
+
+use chess::{ChessMove, Square};
+use iced_chess::{
+    chess::GameState,
+    widget::chess_board::premove::{PieceMap, Premoves},
+};
+
+pub fn mv(source: Square, dest: Square) -> ChessMove {
+    ChessMove::new(source, dest, None)
+}
+
+pub fn state(fen: &str) -> GameState {
+    GameState::from_fen(fen).unwrap()
+}
+
+pub fn pieces(game: &GameState) -> PieceMap {
+    Premoves::default().pieces(&game.board)
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use chess::Square;
+use iced_chess::{
+    chess::GameState,
+    widget::chess_board::cursor::{Focus, step},
+};
+
+use common::pieces;
+
+#[test]
+fn describes_focused_square() {
+    let pieces = pieces(&GameState::default());
+
+    assert_eq!(
+        Focus::new(&pieces, Square::G1, true, false).to_string(),
+        "g1, white knight, selected"
+    );
+    assert_eq!(
+        Focus::new(&pieces, Square::F3, false, true).to_string(),
+        "f3, empty, legal destination"
+    );
+    assert_eq!(
+        Focus::new(&pieces, Square::E8, false, false).to_string(),
+        "e8, black king"
+    );
+}
+
+#[test]
+fn steps_follow_orientation_and_stop_at_edges() {
+    assert_eq!(step(Square::E4, 0, 1, false), Square::E5);
+    assert_eq!(step(Square::E4, 0, 1, true), Square::E3);
+    assert_eq!(step(Square::E4, -1, 0, false), Square::D4);
+    assert_eq!(step(Square::E4, -1, 0, true), Square::F4);
+    assert_eq!(step(Square::A1, -1, -1, false), Square::A1);
+    assert_eq!(step(Square::H8, 1, 1, false), Square::H8);
+}
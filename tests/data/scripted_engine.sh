@@ -0,0 +1,33 @@
+#!/bin/sh
+# Minimal UCI stand-in: answers the handshake and replays a fixed search.
+while read -r line; do
+    case "$line" in
+        uci)
+            echo "id name Scripted Engine"
+            echo "id author iced-chess"
+            echo "option name Hash type spin default 16 min 1 max 1024"
+            echo "option name Ponder type check default false"
+            echo "option name Style type combo default Normal var Solid var Normal var Risky"
+            echo "option name Clear Hash type button"
+            echo "uciok"
+            ;;
+        isready)
+            echo "readyok"
+            ;;
+        "go infinite")
+            echo "info depth 1 score cp 10 pv d2d4"
+            ;;
+        go*)
+            echo "info string $line"
+            echo "info depth 1 seldepth 2 multipv 1 score cp 25 nodes 20 nps 2000 time 10 pv e2e4"
+            echo "info depth 2 multipv 1 score mate 3 lowerbound nodes 400 pv e2e4 e7e5"
+            echo "bestmove e2e4 ponder e7e5"
+            ;;
+        stop)
+            echo "bestmove d2d4"
+            ;;
+        quit)
+            exit 0
+            ;;
+    esac
+done
@@ -0,0 +1,131 @@
+mod common;
+
+use chess::Square;
+use iced_chess::chess::{DrawReason, GameStatus, GameTree};
+
+use common::{mv, state};
+
+fn tree(fen: &str) -> GameTree {
+    GameTree::new(state(fen))
+}
+
+fn status(fen: &str) -> GameStatus {
+    state(fen).status()
+}
+
+fn shuffle(tree: &mut GameTree) {
+    tree.play(mv(Square::G1, Square::F3));
+    tree.play(mv(Square::G8, Square::F6));
+    tree.play(mv(Square::F3, Square::G1));
+    tree.play(mv(Square::F6, Square::G8));
+}
+
+#[test]
+fn threefold_is_claimable_and_fivefold_is_automatic() {
+    let mut tree = GameTree::default();
+
+    shuffle(&mut tree);
+    assert_eq!(tree.current().repetitions, 2);
+    assert_eq!(tree.current().claimable_draw(), None);
+
+    shuffle(&mut tree);
+    assert_eq!(tree.current().repetitions, 3);
+    assert_eq!(
+        tree.current().claimable_draw(),
+        Some(DrawReason::ThreefoldRepetition)
+    );
+    assert_eq!(tree.current().status(), GameStatus::Ongoing);
+
+    shuffle(&mut tree);
+    shuffle(&mut tree);
+    assert_eq!(tree.current().repetitions, 5);
+    assert_eq!(
+        tree.current().status(),
+        GameStatus::Draw(DrawReason::FivefoldRepetition)
+    );
+    assert_eq!(tree.current().claimable_draw(), None);
+}
+
+#[test]
+fn repetitions_follow_the_current_line_only() {
+    let mut tree = GameTree::default();
+    shuffle(&mut tree);
+    shuffle(&mut tree);
+    let threefold = tree.cursor();
+
+    tree.go_start();
+    let sideline = tree.add_move(GameTree::ROOT, mv(Square::B1, Square::C3));
+    tree.go_to(sideline.unwrap());
+    tree.play(mv(Square::B8, Square::C6));
+    tree.play(mv(Square::C3, Square::B1));
+    tree.play(mv(Square::C6, Square::B8));
+
+    assert_eq!(tree.current().repetitions, 2);
+    assert_eq!(tree.state(threefold).unwrap().repetitions, 3);
+}
+
+#[test]
+fn pawn_moves_start_a_new_count() {
+    let mut tree = GameTree::default();
+    shuffle(&mut tree);
+    tree.play(mv(Square::E2, Square::E4));
+    tree.play(mv(Square::E7, Square::E5));
+    shuffle(&mut tree);
+
+    assert_eq!(tree.current().halfmove, 4);
+    assert_eq!(tree.current().repetitions, 2);
+}
+
+#[test]
+fn fifty_and_seventy_five_move_rules() {
+    let mut tree = tree("4k3/8/8/8/8/8/8/R3K3 w - - 99 80");
+    tree.play(mv(Square::A1, Square::A2));
+    assert_eq!(tree.current().halfmove, 100);
+    assert_eq!(tree.current().status(), GameStatus::Ongoing);
+    assert_eq!(
+        tree.current().claimable_draw(),
+        Some(DrawReason::FiftyMoveRule)
+    );
+
+    let mut tree = tree_at(149);
+    tree.play(mv(Square::A1, Square::A2));
+    assert_eq!(tree.current().halfmove, 150);
+    assert_eq!(
+        tree.current().status(),
+        GameStatus::Draw(DrawReason::SeventyFiveMoveRule)
+    );
+    assert_eq!(tree.current().claimable_draw(), None);
+}
+
+fn tree_at(halfmove: u32) -> GameTree {
+    tree(&format!("4k3/8/8/8/8/8/8/R3K3 w - - {halfmove} 80"))
+}
+
+#[test]
+fn dead_positions() {
+    let dead = GameStatus::Draw(DrawReason::InsufficientMaterial);
+
+    assert_eq!(status("8/8/4k3/8/8/8/8/4K3 w - - 0 1"), dead);
+    assert_eq!(status("8/8/4k3/8/8/8/8/2B1K3 w - - 0 1"), dead);
+    assert_eq!(status("8/8/4k3/8/8/8/8/1N2K3 b - - 0 1"), dead);
+    assert_eq!(status("5b2/8/4k3/8/8/8/8/2B1K3 w - - 0 1"), dead);
+
+    assert_eq!(
+        status("2b5/8/4k3/8/8/8/8/2B1K3 w - - 0 1"),
+        GameStatus::Ongoing
+    );
+    assert_eq!(
+        status("8/8/4k3/8/8/8/8/1NN1K3 w - - 0 1"),
+        GameStatus::Ongoing
+    );
+    assert_eq!(
+        status("8/8/4k3/8/8/8/4p3/4K3 w - - 0 1"),
+        GameStatus::Ongoing
+    );
+
+    let mut tree = tree("4k3/8/8/8/8/8/4r3/4K3 w - - 0 1");
+    assert_eq!(tree.current().status(), GameStatus::Ongoing);
+    tree.play(mv(Square::E1, Square::E2));
+    assert_eq!(tree.current().status(), dead);
+    assert!(tree.current().is_dead_position());
+}
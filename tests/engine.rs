@@ -0,0 +1,82 @@
+mod common;
+
+use std::time::Duration;
+
+use chess::{ChessMove, Square};
+use iced_chess::{
+    chess::GameState,
+    engine::{LEVELS, Level, Searcher},
+    uci::Score,
+};
+
+use common::state;
+
+fn exact(depth: u8) -> Level {
+    Level::new(depth, Duration::from_secs(10), 0)
+}
+
+#[test]
+fn finds_mate_in_one() {
+    let game = state("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+    let result = Searcher::new(exact(3)).search(&game, &[]);
+
+    assert_eq!(
+        result.best,
+        Some(ChessMove::new(Square::A1, Square::A8, None))
+    );
+    assert_eq!(result.score, Some(Score::Mate(1)));
+}
+
+#[test]
+fn takes_hanging_queen() {
+    let game = state("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1");
+    let result = Searcher::new(exact(4)).search(&game, &[]);
+
+    assert_eq!(
+        result.best,
+        Some(ChessMove::new(Square::D1, Square::D5, None))
+    );
+    assert!(matches!(result.score, Some(Score::Cp(cp)) if cp > 300));
+    assert_eq!(result.pv.first(), result.best.as_ref());
+}
+
+#[test]
+fn weak_levels_play_legal_moves() {
+    let game = GameState::default();
+    for seed in 1..8 {
+        let result = Searcher::new(Level::strength(1))
+            .seed(seed)
+            .search(&game, &[]);
+        let mv = result.best.unwrap();
+        assert!(game.board.legal(mv));
+        assert_eq!(result.depth, 1);
+    }
+}
+
+#[test]
+fn finished_games_have_no_move() {
+    let mated = state("R5k1/5ppp/8/8/8/8/8/6K1 b - - 1 1");
+    assert_eq!(Searcher::new(exact(2)).search(&mated, &[]).best, None);
+}
+
+#[test]
+fn strength_is_clamped() {
+    assert_eq!(Level::strength(0), LEVELS[0]);
+    assert_eq!(Level::strength(100), LEVELS[LEVELS.len() - 1]);
+}
+
+#[test]
+fn searcher_is_reused_across_moves() {
+    let mut searcher = Searcher::new(exact(3));
+    let game = state("4k3/8/8/3q4/8/8/8/3RK3 w - - 0 1");
+    let first = searcher.search(&game, &[]).best.unwrap();
+
+    let mate = state("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1");
+    let result = searcher.search(&mate, &[]);
+
+    assert_eq!(first, ChessMove::new(Square::D1, Square::D5, None));
+    assert_eq!(
+        result.best,
+        Some(ChessMove::new(Square::A1, Square::A8, None))
+    );
+}
@@ -0,0 +1,24 @@
+use chess::Color;
+use iced_chess::{
+    uci::Score,
+    widget::eval_bar::{format_score, white_share},
+};
+
+#[test]
+fn share_and_label() {
+    assert_eq!(white_share(Score::Cp(0), Color::White), 0.5);
+    assert!(white_share(Score::Cp(150), Color::White) > 0.6);
+    assert!(white_share(Score::Cp(-150), Color::White) < 0.4);
+    assert!(white_share(Score::Cp(5000), Color::White) <= 1.0);
+    assert_eq!(white_share(Score::Mate(3), Color::White), 1.0);
+    assert_eq!(white_share(Score::Mate(-2), Color::White), 0.0);
+
+    assert_eq!(white_share(Score::Mate(0), Color::White), 0.0);
+    assert_eq!(white_share(Score::Mate(0), Color::Black), 1.0);
+    assert_eq!(white_share(Score::Mate(-2), Color::Black), 0.0);
+
+    assert_eq!(format_score(Score::Cp(0)), "0.0");
+    assert_eq!(format_score(Score::Cp(-240)), "2.4");
+    assert_eq!(format_score(Score::Cp(120)), "1.2");
+    assert_eq!(format_score(Score::Mate(-4)), "M4");
+}
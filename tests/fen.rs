@@ -0,0 +1,123 @@
+use chess::{Color, Rank, Square};
+use iced_chess::{
+    chess::GameState,
+    fen::{FenError, STARTING_FEN},
+};
+
+fn error(fen: &str) -> FenError {
+    GameState::from_fen(fen).unwrap_err()
+}
+
+#[test]
+fn round_trips() {
+    for fen in [
+        STARTING_FEN,
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        "r3k2r/8/8/8/8/8/8/R3K2R b Kq - 12 40",
+        "8/5k2/8/8/8/8/2K5/8 w - - 99 120",
+    ] {
+        assert_eq!(GameState::from_fen(fen).unwrap().to_fen(), fen);
+    }
+
+    assert_eq!(GameState::default().to_fen(), STARTING_FEN);
+    assert_eq!(
+        GameState::from_fen("8/5k2/8/8/8/8/2K5/8 w - -")
+            .unwrap()
+            .to_fen(),
+        "8/5k2/8/8/8/8/2K5/8 w - - 0 1"
+    );
+    assert_eq!(
+        "8/5k2/8/8/8/8/2K5/8 b - - 3 7".parse::<GameState>(),
+        GameState::from_fen("8/5k2/8/8/8/8/2K5/8 b - - 3 7")
+    );
+}
+
+#[test]
+fn syntax_errors() {
+    assert_eq!(error("8/8/8 w"), FenError::FieldCount(2));
+    assert_eq!(error("8/8/8/8/8/8/8 w - -"), FenError::RankCount(7));
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K4 w - -"),
+        FenError::RankLength(Rank::First)
+    );
+    assert_eq!(
+        error("4k2/8/8/8/8/8/8/4K3 w - -"),
+        FenError::RankLength(Rank::Eighth)
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4KX2 w - -"),
+        FenError::InvalidPiece('X')
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 x - -"),
+        FenError::InvalidSideToMove("x".into())
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 w KX -"),
+        FenError::InvalidCastling("KX".into())
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 w - e9"),
+        FenError::InvalidEnPassant("e9".into())
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 w - - x 1"),
+        FenError::InvalidHalfmoveClock("x".into())
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K3 w - - 0 0"),
+        FenError::InvalidFullmoveNumber("0".into())
+    );
+}
+
+#[test]
+fn position_errors() {
+    assert_eq!(
+        error("8/8/8/8/8/8/8/4K3 w - -"),
+        FenError::MissingKing(Color::Black)
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/8 w - -"),
+        FenError::MissingKing(Color::White)
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/3KK3 w - -"),
+        FenError::TooManyKings(Color::White)
+    );
+    assert_eq!(
+        error("P3k3/8/8/8/8/8/8/4K3 w - -"),
+        FenError::PawnOnBackRank(Square::A8)
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/8/4K2p b - -"),
+        FenError::PawnOnBackRank(Square::H1)
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/8/4R3/4K3 w - -"),
+        FenError::InactiveSideInCheck(Color::Black)
+    );
+    assert_eq!(
+        error("4k3/8/8/8/8/5n2/8/4K3 b - -"),
+        FenError::InactiveSideInCheck(Color::White)
+    );
+    assert_eq!(
+        error("r3k3/8/8/8/8/8/8/4K2R w Kk -"),
+        FenError::ImpossibleCastling(Color::Black)
+    );
+    assert_eq!(
+        error("r3k2r/8/8/8/8/8/8/R4K1R w Q -"),
+        FenError::ImpossibleCastling(Color::White)
+    );
+    assert_eq!(
+        error("4k3/8/8/3pP3/8/8/8/4K3 w - e6"),
+        FenError::ImpossibleEnPassant(Square::E6)
+    );
+    assert_eq!(
+        error("4k3/8/8/3pP3/8/8/8/4K3 b - d6"),
+        FenError::ImpossibleEnPassant(Square::D6)
+    );
+    assert_eq!(
+        error("4k3/3p4/8/3pP3/8/8/8/4K3 w - d6"),
+        FenError::ImpossibleEnPassant(Square::D6)
+    );
+}
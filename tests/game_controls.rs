@@ -0,0 +1,35 @@
+use chess::{ChessMove, Square};
+use iced::Theme;
+use iced_chess::{
+    chess::GameTree,
+    widget::{GameControls, game_controls::Control},
+};
+
+#[test]
+fn optional_controls_and_enabled_state() {
+    let mut tree = GameTree::default();
+    tree.play(ChessMove::new(Square::E2, Square::E4, None));
+
+    let controls = GameControls::<Control, Theme>::from_tree(&tree).on_control(|control| control);
+    assert_eq!(
+        controls.controls(),
+        [
+            Control::First,
+            Control::Previous,
+            Control::Next,
+            Control::Last,
+            Control::Flip
+        ]
+    );
+    assert!(controls.is_enabled(Control::Previous));
+    assert!(!controls.is_enabled(Control::Last));
+
+    let controls = controls.takeback(true).resign(true);
+    assert_eq!(
+        controls.controls()[5..],
+        [Control::Takeback, Control::Resign]
+    );
+
+    let inert = GameControls::<Control, Theme>::from_tree(&tree);
+    assert!(!inert.is_enabled(Control::Flip));
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use chess::Square;
+use iced_chess::chess::GameTree;
+
+use common::mv;
+
+#[test]
+fn add_move_reuses_existing_child() {
+    let mut tree = GameTree::default();
+    let e4 = tree
+        .add_move(GameTree::ROOT, mv(Square::E2, Square::E4))
+        .unwrap();
+    let d4 = tree
+        .add_move(GameTree::ROOT, mv(Square::D2, Square::D4))
+        .unwrap();
+
+    assert_eq!(
+        tree.add_move(GameTree::ROOT, mv(Square::E2, Square::E4)),
+        Some(e4)
+    );
+    assert_eq!(tree.children(GameTree::ROOT), [e4, d4]);
+    assert_eq!(tree.add_move(99, mv(Square::E2, Square::E4)), None);
+
+    tree.go_to(e4);
+    tree.back();
+    assert_eq!(tree.play(mv(Square::D2, Square::D4)), d4);
+    assert_eq!(tree.children(GameTree::ROOT).len(), 2);
+}
+
+#[test]
+fn cursor_navigation() {
+    let mut tree = GameTree::default();
+    let e4 = tree.play(mv(Square::E2, Square::E4));
+    let e5 = tree.play(mv(Square::E7, Square::E5));
+    let c5 = tree.add_move(e4, mv(Square::C7, Square::C5)).unwrap();
+
+    assert_eq!(tree.cursor(), e5);
+    assert!(!tree.can_go_forward());
+    assert!(!tree.forward());
+
+    assert!(tree.back());
+    assert_eq!(tree.cursor(), e4);
+    assert!(tree.forward());
+    assert_eq!(tree.cursor(), e5);
+
+    assert!(tree.go_to(c5));
+    assert_eq!(tree.ply(c5), 2);
+    assert!(!tree.go_to(42));
+    assert_eq!(tree.cursor(), c5);
+
+    tree.go_start();
+    assert!(!tree.can_go_back());
+    assert!(!tree.back());
+    tree.go_end();
+    assert_eq!(tree.cursor(), e5);
+}
+
+#[test]
+fn promote_and_demote_variations() {
+    let mut tree = GameTree::default();
+    let e4 = tree.play(mv(Square::E2, Square::E4));
+    let e5 = tree.play(mv(Square::E7, Square::E5));
+    let c5 = tree.add_move(e4, mv(Square::C7, Square::C5)).unwrap();
+    let e6 = tree.add_move(e4, mv(Square::E7, Square::E6)).unwrap();
+    let nf3 = tree.add_move(c5, mv(Square::G1, Square::F3)).unwrap();
+
+    assert!(tree.promote_variation(nf3));
+    assert_eq!(tree.children(e4), [c5, e5, e6]);
+    assert!(!tree.promote_variation(c5));
+
+    assert!(tree.demote_variation(c5));
+    assert!(tree.demote_variation(c5));
+    assert_eq!(tree.children(e4), [e5, e6, c5]);
+    assert!(!tree.demote_variation(c5));
+
+    assert!(tree.promote_to_mainline(nf3));
+    assert_eq!(tree.children(e4), [c5, e5, e6]);
+    assert_eq!(tree.mainline(), [GameTree::ROOT, e4, c5, nf3]);
+    assert!(tree.is_mainline(nf3));
+    assert!(!tree.promote_to_mainline(nf3));
+}
+
+#[test]
+fn delete_variation_moves_cursor_out() {
+    let mut tree = GameTree::default();
+    let e4 = tree.play(mv(Square::E2, Square::E4));
+    let e5 = tree.play(mv(Square::E7, Square::E5));
+    let c5 = tree.add_move(e4, mv(Square::C7, Square::C5)).unwrap();
+    let nf3 = tree.add_move(c5, mv(Square::G1, Square::F3)).unwrap();
+
+    tree.go_to(nf3);
+    assert!(tree.delete_variation(c5));
+    assert_eq!(tree.cursor(), e4);
+    assert!(!tree.contains(c5));
+    assert!(!tree.contains(nf3));
+    assert_eq!(tree.children(e4), [e5]);
+
+    tree.go_to(e5);
+    let d5 = tree.add_move(e4, mv(Square::D7, Square::D5)).unwrap();
+    assert!(tree.delete_variation(d5));
+    assert_eq!(tree.cursor(), e5);
+
+    assert!(!tree.delete_variation(GameTree::ROOT));
+    assert!(!tree.delete_variation(c5));
+}
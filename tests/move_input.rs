@@ -0,0 +1,67 @@
+mod common;
+
+use chess::{ChessMove, Piece, Square};
+use iced_chess::{chess::GameState, widget::chess_board::input::MoveInput};
+
+use common::mv;
+
+fn typed(text: &str) -> MoveInput {
+    let mut input = MoveInput::default();
+    for c in text.chars() {
+        input.push(c);
+    }
+    input
+}
+
+#[test]
+fn san_and_uci_resolve_when_unique() {
+    let game = GameState::default();
+
+    assert_eq!(typed("N").resolve(&game), None);
+    assert_eq!(typed("Nf").resolve(&game), None);
+    assert_eq!(
+        typed("Nf3").resolve(&game),
+        Some(mv(Square::G1, Square::F3))
+    );
+    assert_eq!(typed("e4").resolve(&game), Some(mv(Square::E2, Square::E4)));
+    assert_eq!(typed("e2").resolve(&game), None);
+    assert_eq!(
+        typed("e2e4").resolve(&game),
+        Some(mv(Square::E2, Square::E4))
+    );
+    assert_eq!(typed("f4").resolve(&game), Some(mv(Square::F2, Square::F4)));
+    assert!(typed("Qh5").candidates(&game).is_empty());
+}
+
+#[test]
+fn castling_and_promotion_wait_for_ambiguity() {
+    let game = GameState::from_fen("4k3/P7/8/8/8/8/8/R3K2R w KQ - 0 1").unwrap();
+
+    assert_eq!(typed("O-O").resolve(&game), None);
+    assert_eq!(typed("0-0").exact(&game), Some(mv(Square::E1, Square::G1)));
+    assert_eq!(
+        typed("O-O-O").resolve(&game),
+        Some(mv(Square::E1, Square::C1))
+    );
+
+    assert_eq!(typed("a8").candidates(&game).len(), 4);
+    assert_eq!(
+        typed("a8=N").resolve(&game),
+        Some(ChessMove::new(Square::A7, Square::A8, Some(Piece::Knight)))
+    );
+    assert_eq!(
+        typed("a7a8q").resolve(&game),
+        Some(ChessMove::new(Square::A7, Square::A8, Some(Piece::Queen)))
+    );
+}
+
+#[test]
+fn rejects_unrelated_characters() {
+    let mut input = MoveInput::default();
+    assert!(!input.push('z'));
+    assert!(!input.push('F'));
+    assert!(!input.push(' '));
+    assert!(input.push('e'));
+    assert_eq!(input.pop(), Some('e'));
+    assert!(input.is_empty());
+}
@@ -0,0 +1,102 @@
+mod common;
+
+use chess::{ChessMove, Piece, Square};
+use iced_chess::{chess::GameState, notation::NotationError};
+
+use common::state;
+
+#[test]
+fn double_disambiguation() {
+    let game = state("7K/8/8/7k/8/Q7/8/Q1Q5 w - - 0 1");
+
+    let a1 = ChessMove::new(Square::A1, Square::B2, None);
+    let a3 = ChessMove::new(Square::A3, Square::B2, None);
+    let c1 = ChessMove::new(Square::C1, Square::B2, None);
+
+    assert_eq!(game.san(a1), "Qa1b2");
+    assert_eq!(game.san(a3), "Q3b2");
+    assert_eq!(game.san(c1), "Qcb2");
+
+    assert_eq!(game.parse_san("Qa1b2"), Ok(a1));
+    assert_eq!(game.parse_san("Q3b2"), Ok(a3));
+    assert_eq!(game.parse_san("Qcb2"), Ok(c1));
+    assert_eq!(
+        game.parse_san("Qb2"),
+        Err(NotationError::Ambiguous("Qb2".into()))
+    );
+    assert_eq!(
+        game.parse_san("Qab2"),
+        Err(NotationError::Ambiguous("Qab2".into()))
+    );
+}
+
+#[test]
+fn en_passant_with_check() {
+    let game = state("8/4k3/8/3pP3/8/8/8/4K3 w - d6 0 2");
+    let mv = ChessMove::new(Square::E5, Square::D6, None);
+
+    assert_eq!(game.san(mv), "exd6+");
+    assert_eq!(game.lan(mv), "e5xd6+");
+    assert_eq!(game.parse_san("exd6+"), Ok(mv));
+    assert_eq!(game.parse_san("exd6"), Ok(mv));
+    assert_eq!(game.parse_move("e5d6"), Ok(mv));
+
+    let after = game.make_move(mv);
+    let ant = after.annotation.unwrap();
+    assert_eq!(ant.capture, Some(Piece::Pawn));
+    assert!(ant.check);
+    assert_eq!(after.halfmove, 0);
+}
+
+#[test]
+fn underpromotion() {
+    let game = state("8/5P1k/8/8/8/8/8/4K3 w - - 0 1");
+    let knight = ChessMove::new(Square::F7, Square::F8, Some(Piece::Knight));
+    let rook = ChessMove::new(Square::F7, Square::F8, Some(Piece::Rook));
+
+    assert_eq!(game.san(knight), "f8=N+");
+    assert_eq!(game.san(rook), "f8=R");
+
+    assert_eq!(game.parse_san("f8=N+"), Ok(knight));
+    assert_eq!(game.parse_san("f8N"), Ok(knight));
+    assert_eq!(game.parse_uci("f7f8r"), Ok(rook));
+    assert_eq!(game.parse_move("f7f8n"), Ok(knight));
+    assert_eq!(
+        game.parse_san("f8"),
+        Err(NotationError::Illegal("f8".into()))
+    );
+}
+
+#[test]
+fn castling_and_mate() {
+    let mut game = GameState::default();
+    for san in ["f3", "e5", "g4"] {
+        game = game.make_move(game.parse_san(san).unwrap());
+    }
+    let mate = game.parse_san("Qh4").unwrap();
+    assert_eq!(game.san(mate), "Qh4#");
+
+    let game = state("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+    let short = game.parse_san("O-O").unwrap();
+    let long = game.parse_san("0-0-0").unwrap();
+    assert_eq!(short, ChessMove::new(Square::E1, Square::G1, None));
+    assert_eq!(game.san(short), "O-O");
+    assert_eq!(game.san(long), "O-O-O");
+    assert_eq!(game.lan(long), "O-O-O");
+}
+
+#[test]
+fn invalid_notation() {
+    let game = GameState::default();
+
+    assert_eq!(game.parse_move(""), Err(NotationError::Empty));
+    assert_eq!(
+        game.parse_move("Zz9"),
+        Err(NotationError::Invalid("Zz9".into()))
+    );
+    assert_eq!(
+        game.parse_move("e2e5"),
+        Err(NotationError::Illegal("e2e5".into()))
+    );
+    assert_eq!(game.lan(game.parse_move("Ng1-f3").unwrap()), "Ng1-f3");
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use chess::Square;
+use iced_chess::{
+    chess::{GameOutcome, GameTree},
+    notation::nag_glyph,
+    widget::notation_panel::{Token, tokens},
+};
+
+use common::mv;
+
+fn texts(tree: &GameTree) -> Vec<String> {
+    tokens(tree)
+        .iter()
+        .map(|token| token.text().to_string())
+        .collect()
+}
+
+#[test]
+fn pairs_sidelines_and_comments() {
+    let mut tree = GameTree::default();
+    let e4 = tree.play(mv(Square::E2, Square::E4));
+    let e5 = tree.play(mv(Square::E7, Square::E5));
+    tree.play(mv(Square::G1, Square::F3));
+
+    let c5 = tree.add_move(e4, mv(Square::C7, Square::C5)).unwrap();
+    tree.add_move(c5, mv(Square::G1, Square::F3)).unwrap();
+
+    tree.set_nags(e5, vec![1, 16]);
+    tree.set_comment(e5, "solid reply");
+
+    assert_eq!(
+        texts(&tree),
+        [
+            "1.", "e4", "e5! ±", "solid", "reply", "(", "1...", "c5", "2.", "Nf3", ")", "2.", "Nf3"
+        ]
+    );
+
+    let tokens = tokens(&tree);
+    assert_eq!(tokens[1].node(), Some(e4));
+    assert!(matches!(tokens[7], Token::Move { node, depth: 1, .. } if node == c5));
+    assert!(matches!(tokens[12], Token::Move { depth: 0, .. }));
+}
+
+#[test]
+fn result_and_glyphs() {
+    let mut tree = GameTree::default();
+    tree.play(mv(Square::F2, Square::F3));
+    tree.set_outcome(Some(GameOutcome::resignation(chess::Color::White)));
+
+    assert_eq!(texts(&tree), ["1.", "f3", "0-1"]);
+    assert_eq!(tokens(&GameTree::default()), []);
+
+    assert_eq!(nag_glyph(3), Some("!!"));
+    assert_eq!(nag_glyph(14), Some("⩲"));
+    assert_eq!(nag_glyph(255), None);
+}
@@ -0,0 +1,197 @@
+use chess::{ChessMove, Color, Square};
+use iced_chess::{
+    chess::{GameOutcome, GameState, GameTree, Termination},
+    pgn::{self, Game, GameResult, PgnError, PgnErrorKind},
+};
+
+const ANNOTATED: &str = r#"[Event "Casual \"blitz\""]
+[Site "?"]
+[Date "2024.01.02"]
+[Round "1"]
+[White "A\\B"]
+[Black "C"]
+[Result "1-0"]
+
+{Opening comment} 1. e4 $1 {best by test} e5 (1... c5 2. Nf3 (2. c3 d5) 2... d6)
+2. Nf3 Nc6 3. Bb5 a6?! 1-0
+"#;
+
+fn error(input: &str) -> PgnError {
+    input.parse::<Game>().unwrap_err()
+}
+
+#[test]
+fn annotated_game_round_trips() {
+    let game: Game = ANNOTATED.parse().unwrap();
+    assert_eq!(game.tag("Event"), Some("Casual \"blitz\""));
+    assert_eq!(game.tag("White"), Some("A\\B"));
+    assert_eq!(
+        game.tree.node(GameTree::ROOT).unwrap().comment,
+        "Opening comment"
+    );
+
+    let e4 = game.tree.children(GameTree::ROOT)[0];
+    let node = game.tree.node(e4).unwrap();
+    assert_eq!(node.nags, [1]);
+    assert_eq!(node.comment, "best by test");
+    assert_eq!(game.tree.children(e4).len(), 2);
+
+    let written = game.to_pgn();
+    assert!(written.lines().all(|line| line.len() <= 80));
+    let movetext = written.split_whitespace().collect::<Vec<_>>().join(" ");
+    assert!(movetext.contains("(1... c5 2. Nf3 (2. c3 d5) 2... d6) 2. Nf3"));
+    assert!(movetext.ends_with("3. Bb5 a6 $6 1-0"));
+
+    let reparsed: Game = written.parse().unwrap();
+    assert_eq!(reparsed.tree, game.tree);
+    assert_eq!(reparsed.to_pgn(), written);
+}
+
+#[test]
+fn multiple_games_round_trip() {
+    let input =
+        "[Result \"1/2-1/2\"]\n\n1. d4 d5 1/2-1/2\n\n[Result \"*\"]\n\n1. c4 ; side note\n*\n";
+    let games = pgn::parse(input).unwrap();
+    assert_eq!(games.len(), 2);
+    assert!(games[0].tree.outcome().unwrap().is_draw());
+    assert_eq!(games[1].tree.outcome(), None);
+    assert_eq!(
+        games[1]
+            .tree
+            .node(games[1].tree.mainline()[1])
+            .unwrap()
+            .comment,
+        "side note"
+    );
+
+    let written = pgn::write(&games);
+    let reparsed = pgn::parse(&written).unwrap();
+    assert_eq!(reparsed.len(), 2);
+    assert_eq!(pgn::write(&reparsed), written);
+    for (a, b) in games.iter().zip(&reparsed) {
+        assert_eq!(a.tree, b.tree);
+    }
+}
+
+#[test]
+fn set_up_position_round_trips() {
+    let input = "[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 12\"]\n\n12... Kd7 13. e4 *\n";
+    let game: Game = input.parse().unwrap();
+    assert_eq!(game.tree.mainline().len(), 3);
+
+    let written = game.to_pgn();
+    assert!(written.contains("12... Kd7 13. e4 *"));
+    assert_eq!(written.parse::<Game>().unwrap().tree, game.tree);
+}
+
+#[test]
+fn outcomes_map_to_result_and_termination_tags() {
+    let bare_kings = GameState::from_fen("8/5k2/8/8/8/8/2K5/8 w - - 0 1").unwrap();
+    let cases = [
+        (
+            GameOutcome::resignation(Color::Black),
+            GameResult::WhiteWins,
+            "normal",
+            Termination::Resignation,
+        ),
+        (
+            GameOutcome::timeout(Color::White, &GameState::default()),
+            GameResult::BlackWins,
+            "time forfeit",
+            Termination::Timeout,
+        ),
+        (
+            GameOutcome::timeout(Color::White, &bare_kings),
+            GameResult::Draw,
+            "time forfeit",
+            Termination::Timeout,
+        ),
+        (
+            GameOutcome::draw_agreement(),
+            GameResult::Draw,
+            "normal",
+            Termination::DrawAgreement,
+        ),
+    ];
+
+    for (outcome, result, tag, termination) in cases {
+        let mut game = Game::default();
+        game.tree.play(ChessMove::new(Square::E2, Square::E4, None));
+        game.tree.set_outcome(Some(outcome));
+        assert_eq!(game.result(), result);
+
+        let written = game.to_pgn();
+        assert!(written.contains(&format!("[Result \"{}\"]", result.as_str())));
+        assert!(written.contains(&format!("[Termination \"{tag}\"]")));
+
+        let reparsed: Game = written.parse().unwrap();
+        assert_eq!(reparsed.tag("Termination"), Some(tag));
+        assert_eq!(
+            reparsed.tree.outcome(),
+            Some(GameOutcome {
+                winner: outcome.winner,
+                termination,
+            })
+        );
+    }
+}
+
+#[test]
+fn errors_report_line_and_column() {
+    let err = error("[Event \"x\"]\n\n1. e4 e5 2. Qxx4 *");
+    assert_eq!((err.line, err.column), (3, 13));
+    assert!(matches!(err.kind, PgnErrorKind::InvalidMove(_)));
+
+    assert_eq!(
+        error("[Event \"abc\\"),
+        PgnError {
+            line: 1,
+            column: 8,
+            kind: PgnErrorKind::UnterminatedString,
+        }
+    );
+    assert_eq!(
+        error("[Event \"abc\n\"]"),
+        PgnError {
+            line: 1,
+            column: 8,
+            kind: PgnErrorKind::UnterminatedString,
+        }
+    );
+    assert_eq!(
+        error("1. e4 {never closed"),
+        PgnError {
+            line: 1,
+            column: 7,
+            kind: PgnErrorKind::UnterminatedComment,
+        }
+    );
+    assert_eq!(
+        error("1. e4\n  & e5"),
+        PgnError {
+            line: 2,
+            column: 3,
+            kind: PgnErrorKind::UnexpectedChar('&'),
+        }
+    );
+    assert_eq!(
+        error("1. e4 e5 ) *"),
+        PgnError {
+            line: 1,
+            column: 10,
+            kind: PgnErrorKind::UnbalancedVariation,
+        }
+    );
+    assert_eq!(
+        error("1. e4 $ e5"),
+        PgnError {
+            line: 1,
+            column: 7,
+            kind: PgnErrorKind::InvalidNag(String::new()),
+        }
+    );
+    assert!(matches!(
+        error("[FEN \"8/8/8/8/8/8/8/8 w - - 0 1\"]\n\n*").kind,
+        PgnErrorKind::InvalidFen(_)
+    ));
+}
@@ -0,0 +1,116 @@
+use chess::{ALL_COLORS, ALL_PIECES, Color, Piece};
+use iced::advanced::image::Handle;
+use iced_chess::{
+    notation::piece_char,
+    piece_set::{Format, PieceSet, PieceSetError},
+};
+
+const SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="45" height="45"><circle cx="22.5" cy="22.5" r="15" fill="black"/></svg>"#;
+
+fn name(piece: Piece, color: Color, ext: &str) -> String {
+    let side = if color == Color::White { 'w' } else { 'b' };
+    format!("{side}{}.{ext}", piece_char(piece))
+}
+
+fn svg_files() -> Vec<(String, Vec<u8>)> {
+    ALL_COLORS
+        .iter()
+        .flat_map(|&color| ALL_PIECES.iter().map(move |&piece| (piece, color)))
+        .map(|(piece, color)| (name(piece, color, "svg"), SVG.as_bytes().to_vec()))
+        .collect()
+}
+
+fn with(name: String, data: &[u8]) -> Vec<(String, Vec<u8>)> {
+    let mut files = svg_files();
+    files.retain(|(n, _)| *n != name);
+    files.push((name, data.to_vec()));
+    files
+}
+
+#[test]
+fn detects_formats() {
+    assert_eq!(Format::detect(b"\x89PNG\r\n\x1a\nrest"), Some(Format::Png));
+    assert_eq!(Format::detect(b"\n  <svg/>"), Some(Format::Svg));
+    assert_eq!(Format::detect(b"\xef\xbb\xbf<?xml?>"), Some(Format::Svg));
+    assert_eq!(Format::detect(b"GIF89a"), None);
+    assert_eq!(Format::detect(b""), None);
+}
+
+#[test]
+fn loads_embedded_and_directory_sets() {
+    assert_eq!(PieceSet::default(), PieceSet::default());
+    assert!(!PieceSet::default().is_vector());
+
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/pieces");
+    let set = PieceSet::from_dir(dir).unwrap();
+    assert!(!set.is_vector());
+    assert_ne!(set, PieceSet::default());
+    assert_eq!(
+        set.get(Piece::Queen, Color::White, 40.0),
+        set.get(Piece::Queen, Color::White, 80.0)
+    );
+}
+
+#[test]
+fn svg_sets_rasterize_per_size() {
+    let set = PieceSet::from_files(svg_files()).unwrap();
+    assert!(set.is_vector());
+
+    let small = set.get(Piece::Knight, Color::Black, 32.0);
+    let large = set.get(Piece::Knight, Color::Black, 96.0);
+    assert_ne!(small, large);
+    assert_eq!(small, set.get(Piece::Knight, Color::Black, 31.6));
+    assert!(matches!(
+        large,
+        Handle::Rgba {
+            width: 96,
+            height: 96,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn reports_missing_and_corrupt_files() {
+    let mut files = svg_files();
+    files.retain(|(n, _)| *n != "bK.svg");
+    assert_eq!(
+        PieceSet::from_files(files).unwrap_err(),
+        PieceSetError::Missing(Piece::King, Color::Black)
+    );
+
+    let mut files = with(
+        name(Piece::Pawn, Color::White, "png"),
+        b"\x89PNG\r\n\x1a\ngarbage",
+    );
+    files.retain(|(n, _)| *n != "wP.svg");
+    assert!(matches!(
+        PieceSet::from_files(files),
+        Err(PieceSetError::InvalidPng(Piece::Pawn, Color::White, _))
+    ));
+
+    let files = with(name(Piece::Rook, Color::White, "svg"), b"<svg");
+    assert!(matches!(
+        PieceSet::from_files(files),
+        Err(PieceSetError::InvalidSvg(Piece::Rook, Color::White, _))
+    ));
+
+    let files = with(name(Piece::Bishop, Color::Black, "svg"), b"GIF89a");
+    assert_eq!(
+        PieceSet::from_files(files).unwrap_err(),
+        PieceSetError::UnknownFormat(Piece::Bishop, Color::Black)
+    );
+
+    assert!(matches!(
+        PieceSet::from_dir("/nonexistent/piece/set"),
+        Err(PieceSetError::Io(path, _)) if path.as_os_str() == "/nonexistent/piece/set"
+    ));
+    assert!(matches!(
+        PieceSet::from_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml")),
+        Err(PieceSetError::Io(..))
+    ));
+    assert_eq!(
+        PieceSet::from_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data")).unwrap_err(),
+        PieceSetError::Missing(Piece::Pawn, Color::White)
+    );
+}
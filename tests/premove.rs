@@ -0,0 +1,93 @@
+mod common;
+
+use chess::{Board, Piece, Square};
+use iced_chess::widget::chess_board::premove::{Premoves, follows, legal};
+
+use common::mv;
+
+fn dests(premoves: &Premoves, board: &Board, square: Square) -> Vec<Square> {
+    let mut dests: Vec<Square> = premoves
+        .dests(board, square)
+        .iter()
+        .map(|mv| mv.get_dest())
+        .collect();
+    dests.sort_by_key(|sq| sq.to_index());
+    dests
+}
+
+#[test]
+fn chained_premoves_build_on_each_other() {
+    let board = Board::default();
+    let mut premoves = Premoves::default();
+
+    premoves.push(mv(Square::E2, Square::E4), true);
+    premoves.push(mv(Square::E4, Square::E5), true);
+    assert_eq!(premoves.moves().len(), 2);
+
+    let pieces = premoves.pieces(&board);
+    assert_eq!(pieces[Square::E2.to_index()], None);
+    assert_eq!(pieces[Square::E4.to_index()], None);
+    assert_eq!(
+        pieces[Square::E5.to_index()].map(|(piece, _)| piece),
+        Some(Piece::Pawn)
+    );
+    assert_eq!(
+        dests(&premoves, &board, Square::E5),
+        [Square::D6, Square::E6, Square::F6]
+    );
+
+    premoves.push(mv(Square::D2, Square::D4), false);
+    assert_eq!(premoves.moves(), [mv(Square::D2, Square::D4)]);
+
+    assert_eq!(premoves.pop_front(), Some(mv(Square::D2, Square::D4)));
+    assert!(premoves.is_empty());
+}
+
+#[test]
+fn dests_ignore_blockers_but_not_own_pieces() {
+    let board = Board::default();
+    let premoves = Premoves::default();
+
+    assert_eq!(
+        dests(&premoves, &board, Square::A1),
+        [
+            Square::A3,
+            Square::A4,
+            Square::A5,
+            Square::A6,
+            Square::A7,
+            Square::A8
+        ]
+    );
+    assert_eq!(
+        dests(&premoves, &board, Square::E2),
+        [Square::D3, Square::E3, Square::F3, Square::E4]
+    );
+    assert!(dests(&premoves, &board, Square::E4).is_empty());
+}
+
+#[test]
+fn illegal_premoves_are_discarded() {
+    let board = Board::default();
+    let e4 = board.make_move_new(mv(Square::E2, Square::E4));
+    let e5 = e4.make_move_new(mv(Square::E7, Square::E5));
+
+    assert_eq!(
+        legal(&e5, mv(Square::G1, Square::F3)),
+        Some(mv(Square::G1, Square::F3))
+    );
+    assert_eq!(legal(&e5, mv(Square::E4, Square::E5)), None);
+    assert_eq!(legal(&e5, mv(Square::A1, Square::A3)), None);
+}
+
+#[test]
+fn only_single_ply_continuations_keep_the_queue() {
+    let board = Board::default();
+    let e4 = board.make_move_new(mv(Square::E2, Square::E4));
+    let e5 = e4.make_move_new(mv(Square::E7, Square::E5));
+
+    assert!(follows(&board, &e4));
+    assert!(follows(&e4, &e5));
+    assert!(!follows(&board, &e5));
+    assert!(!follows(&e5, &e4));
+}
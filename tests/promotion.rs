@@ -0,0 +1,59 @@
+use chess::{ChessMove, Piece, Square};
+use iced_chess::widget::chess_board::{
+    cursor::step,
+    overlay::{PROMOTION_PIECES, pending_promotion, promotion_choices_for},
+};
+
+#[test]
+fn choices_run_from_the_promotion_square_toward_the_centre() {
+    let white = ChessMove::new(Square::A7, Square::A8, None);
+    assert_eq!(
+        promotion_choices_for(white),
+        [
+            (Square::A8, Piece::Queen),
+            (Square::A7, Piece::Knight),
+            (Square::A6, Piece::Rook),
+            (Square::A5, Piece::Bishop),
+        ]
+    );
+
+    let black = ChessMove::new(Square::G2, Square::H1, None);
+    assert_eq!(
+        promotion_choices_for(black),
+        [
+            (Square::H1, Piece::Queen),
+            (Square::H2, Piece::Knight),
+            (Square::H3, Piece::Rook),
+            (Square::H4, Piece::Bishop),
+        ]
+    );
+
+    for (mv, top) in [(white, true), (black, false)] {
+        for flipped in [false, true] {
+            let up = if top != flipped { -1 } else { 1 };
+            let choices = promotion_choices_for(mv);
+            for pair in choices.windows(2) {
+                assert_eq!(step(pair[0].0, 0, up, flipped), pair[1].0);
+            }
+            assert_eq!(
+                choices.iter().map(|&(_, piece)| piece).collect::<Vec<_>>(),
+                PROMOTION_PIECES
+            );
+        }
+    }
+}
+
+#[test]
+fn auto_queen_skips_the_picker() {
+    let queen = ChessMove::new(Square::A7, Square::A8, Some(Piece::Queen));
+
+    assert_eq!(
+        pending_promotion(queen, false),
+        Some(ChessMove::new(Square::A7, Square::A8, None))
+    );
+    assert_eq!(pending_promotion(queen, true), None);
+    assert_eq!(
+        pending_promotion(ChessMove::new(Square::E2, Square::E4, None), false),
+        None
+    );
+}
@@ -0,0 +1,131 @@
+mod common;
+
+use chess::{CastleRights, Color, Piece, Square};
+use iced::{Point, Rectangle, Size};
+use iced_chess::{
+    chess::GameState,
+    fen::{FenError, STARTING_FEN},
+    setup::Setup,
+    widget::{chess_board::editor, setup_panel::Action},
+};
+
+use common::state;
+
+fn setup(fen: &str) -> Setup {
+    Setup::from_state(&state(fen))
+}
+
+#[test]
+fn start_position_round_trips() {
+    let setup = Setup::default();
+
+    assert_eq!(setup.to_fen(), STARTING_FEN);
+    assert_eq!(setup.game().unwrap(), GameState::default());
+    assert_eq!(Setup::from_state(&GameState::default()), setup);
+}
+
+#[test]
+fn free_placement_and_removal() {
+    let mut setup = Setup::empty();
+    setup.set(Square::E1, Some((Piece::King, Color::White)));
+    assert_eq!(setup.game(), Err(FenError::MissingKing(Color::Black)));
+
+    setup.set(Square::E8, Some((Piece::King, Color::Black)));
+    setup.set(Square::D4, Some((Piece::Queen, Color::White)));
+    setup.relocate(Square::D4, Square::H4);
+    assert_eq!(setup.piece(Square::D4), None);
+    assert_eq!(setup.to_fen(), "4k3/8/8/8/7Q/8/8/4K3 w - - 0 1");
+    assert!(setup.game().is_ok());
+
+    setup.set(Square::H4, None);
+    setup.set(Square::A8, Some((Piece::Pawn, Color::White)));
+    assert_eq!(setup.game(), Err(FenError::PawnOnBackRank(Square::A8)));
+}
+
+#[test]
+fn castling_rights_are_validated() {
+    let mut setup = setup("r3k2r/8/8/8/8/8/8/R3K2R w - - 0 1");
+    setup.toggle_castling(Color::White, CastleRights::KingSide);
+    setup.toggle_castling(Color::Black, CastleRights::QueenSide);
+    assert_eq!(setup.to_fen(), "r3k2r/8/8/8/8/8/8/R3K2R w Kq - 0 1");
+
+    setup.toggle_castling(Color::White, CastleRights::KingSide);
+    assert_eq!(setup.castling[0], CastleRights::NoRights);
+
+    setup.set(Square::A8, None);
+    assert_eq!(
+        setup.game(),
+        Err(FenError::ImpossibleCastling(Color::Black))
+    );
+}
+
+#[test]
+fn en_passant_candidates() {
+    let setup = setup("4k3/8/8/3pP3/8/8/8/4K3 w - - 0 1");
+    assert_eq!(setup.en_passant_squares(), vec![Square::D6]);
+
+    let with_ep = Action::EnPassant(Some(Square::D6)).apply(&setup);
+    assert_eq!(with_ep.to_fen(), "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1");
+    assert!(with_ep.game().is_ok());
+
+    let black = Action::Side(Color::Black).apply(&with_ep);
+    assert_eq!(black.en_passant, None);
+    assert!(black.en_passant_squares().is_empty());
+}
+
+#[test]
+fn mirror_and_flip_colors() {
+    let setup = setup("4k3/8/8/3pP3/8/8/8/R3K3 w Q d6 0 1");
+
+    let mirrored = setup.mirror();
+    assert_eq!(mirrored.to_fen(), "3k4/8/8/3Pp3/8/8/8/3K3R w - e6 0 1");
+    assert_eq!(mirrored.mirror().castling, [CastleRights::NoRights; 2]);
+
+    let flipped = setup.flip_colors();
+    assert_eq!(flipped.to_fen(), "r3k3/8/8/8/3Pp3/8/8/4K3 b q d3 0 1");
+    assert!(flipped.game().is_ok());
+    assert_eq!(flipped.flip_colors(), setup);
+}
+
+#[test]
+fn panel_actions() {
+    let setup = Setup::default();
+
+    assert_eq!(Action::Clear.apply(&setup), Setup::empty());
+    assert_eq!(Action::Start.apply(&Setup::empty()), setup);
+    assert!(Action::Side(Color::White).is_active(&setup));
+    assert!(Action::Castling(Color::Black, CastleRights::QueenSide).is_active(&setup));
+    assert!(
+        !Action::Castling(Color::Black, CastleRights::QueenSide)
+            .apply(&setup)
+            .castling[1]
+            .has_queenside()
+    );
+}
+
+#[test]
+fn editor_palette_surrounds_board() {
+    let bounds = Rectangle::new(Point::ORIGIN, Size::new(400.0, 500.0));
+
+    assert_eq!(
+        editor::board_bounds(bounds),
+        Rectangle::new(Point::new(0.0, 50.0), Size::new(400.0, 400.0))
+    );
+    assert_eq!(editor::palette(bounds, false).len(), 12);
+    assert_eq!(
+        editor::spare_at(bounds, false, Point::new(75.0, 25.0)),
+        Some((Piece::King, Color::Black))
+    );
+    assert_eq!(
+        editor::spare_at(bounds, true, Point::new(75.0, 25.0)),
+        Some((Piece::King, Color::White))
+    );
+    assert_eq!(
+        editor::spare_at(bounds, false, Point::new(325.0, 475.0)),
+        Some((Piece::Pawn, Color::White))
+    );
+    assert_eq!(
+        editor::spare_at(bounds, false, Point::new(25.0, 25.0)),
+        None
+    );
+}
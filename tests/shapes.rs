@@ -0,0 +1,264 @@
+use chess::{ChessMove, Square};
+use iced::{Color, keyboard::Modifiers};
+use iced_chess::{
+    chess::GameTree,
+    opiece::font,
+    pgn::{self, Game},
+    style::chess_board::Brushes,
+    uci::{Info, Score},
+    widget::chess_board::shape::{self, Brush, Mark, Shape},
+};
+
+fn brushes() -> Brushes {
+    Brushes {
+        green: Color::from_rgb(0.0, 1.0, 0.0),
+        red: Color::from_rgb(1.0, 0.0, 0.0),
+        blue: Color::from_rgb(0.0, 0.0, 1.0),
+        yellow: Color::from_rgb(1.0, 1.0, 0.0),
+    }
+}
+
+#[test]
+fn constructors_set_mark_and_full_opacity() {
+    let red = Color::from_rgb8(235, 97, 80);
+
+    let arrow = Shape::arrow(Square::E2, Square::E4, red);
+    assert_eq!(
+        arrow.mark,
+        Mark::Arrow {
+            from: Square::E2,
+            to: Square::E4
+        }
+    );
+    assert_eq!(arrow.opacity, 1.0);
+    assert_eq!(arrow.paint(&brushes()), red);
+
+    assert_eq!(Shape::fill(Square::D4, red).mark, Mark::Fill(Square::D4));
+    assert_eq!(
+        Shape::circle(Square::D5, red).mark,
+        Mark::Circle(Square::D5)
+    );
+    assert_eq!(
+        Shape::glyph(Square::F7, "!!", red).mark,
+        Mark::Glyph(Square::F7, "!!".to_string())
+    );
+}
+
+#[test]
+fn opacity_scales_alpha() {
+    let translucent = Color::from_rgba(0.0, 0.5, 1.0, 0.8);
+
+    let shape = Shape::fill(Square::A1, translucent).opacity(0.5);
+    assert_eq!(
+        shape.paint(&brushes()),
+        Color::from_rgba(0.0, 0.5, 1.0, 0.4)
+    );
+
+    assert_eq!(
+        Shape::fill(Square::A1, translucent).opacity(2.0).opacity,
+        1.0
+    );
+    assert_eq!(
+        Shape::fill(Square::A1, translucent).opacity(-1.0).opacity,
+        0.0
+    );
+}
+
+#[test]
+fn modifiers_pick_brushes() {
+    assert_eq!(Brush::from_modifiers(Modifiers::empty()), Brush::Green);
+    assert_eq!(Brush::from_modifiers(Modifiers::SHIFT), Brush::Red);
+    assert_eq!(Brush::from_modifiers(Modifiers::CTRL), Brush::Red);
+    assert_eq!(Brush::from_modifiers(Modifiers::ALT), Brush::Blue);
+    assert_eq!(
+        Brush::from_modifiers(Modifiers::SHIFT | Modifiers::ALT),
+        Brush::Yellow
+    );
+
+    let shape = Shape::circle(Square::E4, Brush::Blue).opacity(0.5);
+    assert_eq!(
+        shape.paint(&brushes()),
+        Color::from_rgba(0.0, 0.0, 1.0, 0.5)
+    );
+}
+
+#[test]
+fn toggling_adds_recolors_and_removes() {
+    let mut shapes = Vec::new();
+
+    shape::toggle(&mut shapes, Shape::circle(Square::E4, Brush::Green));
+    shape::toggle(
+        &mut shapes,
+        Shape::arrow(Square::G1, Square::F3, Brush::Green),
+    );
+    assert_eq!(shapes.len(), 2);
+
+    shape::toggle(&mut shapes, Shape::circle(Square::E4, Brush::Red));
+    assert_eq!(shapes.len(), 2);
+    assert_eq!(shapes[0].brush, Brush::Red);
+
+    shape::toggle(&mut shapes, Shape::circle(Square::E4, Brush::Red));
+    assert_eq!(
+        shapes,
+        vec![Shape::arrow(Square::G1, Square::F3, Brush::Green)]
+    );
+}
+
+#[test]
+fn comment_commands_round_trip() {
+    let shapes = vec![
+        Shape::arrow(Square::G1, Square::F3, Brush::Green),
+        Shape::circle(Square::E4, Brush::Red),
+        Shape::arrow(Square::D2, Square::D4, Brush::Yellow),
+        Shape::circle(Square::D5, Brush::Blue),
+        Shape::glyph(Square::E4, "!", Brush::Green),
+        Shape::circle(Square::A1, Color::WHITE),
+    ];
+
+    assert_eq!(
+        shape::to_commands(&shapes),
+        "[%csl Re4,Bd5][%cal Gg1f3,Yd2d4]"
+    );
+    assert_eq!(
+        shape::from_comment("[%csl Re4,Bd5][%cal Gg1f3,Yd2d4]"),
+        vec![
+            Shape::circle(Square::E4, Brush::Red),
+            Shape::circle(Square::D5, Brush::Blue),
+            Shape::arrow(Square::G1, Square::F3, Brush::Green),
+            Shape::arrow(Square::D2, Square::D4, Brush::Yellow),
+        ]
+    );
+    assert_eq!(shape::from_comment("[%cal Xe2e4,Ge2,Gz9e4] text"), vec![]);
+}
+
+#[test]
+fn write_comment_keeps_text_and_other_commands() {
+    let comment = "good move [%clk 0:05:00] [%cal Re2e4]";
+    let shapes = vec![Shape::circle(Square::E4, Brush::Green)];
+
+    assert_eq!(
+        pgn::split_commands(comment),
+        (
+            "good move".to_string(),
+            vec![
+                ("clk".to_string(), "0:05:00".to_string()),
+                ("cal".to_string(), "Re2e4".to_string()),
+            ]
+        )
+    );
+    assert_eq!(
+        shape::write_comment(comment, &shapes),
+        "[%clk 0:05:00][%csl Ge4] good move"
+    );
+    assert_eq!(
+        shape::write_comment(comment, &[]),
+        "[%clk 0:05:00] good move"
+    );
+    assert_eq!(shape::write_comment("[%cal Re2e4]", &[]), "");
+}
+
+#[test]
+fn drawings_survive_pgn() {
+    let mut game = Game::default();
+    let node = game
+        .tree
+        .add_move(GameTree::ROOT, ChessMove::new(Square::E2, Square::E4, None))
+        .unwrap();
+    let shapes = vec![
+        Shape::arrow(Square::E7, Square::E5, Brush::Blue),
+        Shape::circle(Square::D5, Brush::Red),
+    ];
+    game.tree
+        .set_comment(node, shape::write_comment("", &shapes));
+
+    let parsed: Game = game.to_pgn().parse().unwrap();
+    let node = parsed.tree.children(GameTree::ROOT)[0];
+    let comment = &parsed.tree.node(node).unwrap().comment;
+
+    assert_eq!(
+        shape::from_comment(comment),
+        vec![
+            Shape::circle(Square::D5, Brush::Red),
+            Shape::arrow(Square::E7, Square::E5, Brush::Blue),
+        ]
+    );
+}
+
+#[test]
+fn weight_scales_thickness_and_alpha() {
+    let full = Shape::arrow(Square::E2, Square::E4, Brush::Green);
+    let light = full.clone().weight(0.0);
+
+    assert_eq!(full.thickness(), 1.0);
+    assert_eq!(full.alpha(), 1.0);
+    assert!(light.thickness() < full.thickness());
+    assert!(light.paint(&brushes()).a < full.paint(&brushes()).a);
+    assert_eq!(full.clone().weight(3.0).weight, 1.0);
+}
+
+#[test]
+fn arrows_and_labels_to_the_same_square_stack() {
+    let shapes = vec![
+        Shape::arrow(Square::G1, Square::F3, Brush::Green).label("+0.8"),
+        Shape::arrow(Square::E2, Square::E4, Brush::Green).label("+0.5"),
+        Shape::arrow(Square::G2, Square::F3, Brush::Blue),
+        Shape::arrow(Square::E2, Square::F3, Brush::Green).label("#3"),
+        Shape::circle(Square::F3, Brush::Red),
+    ];
+
+    assert_eq!(
+        shape::label_stacks(&shapes),
+        vec![Some((0, 2)), Some((0, 1)), None, Some((1, 2)), None]
+    );
+    assert_eq!(
+        shape::arrow_stacks(&shapes),
+        vec![Some((0, 3)), Some((0, 1)), Some((1, 3)), Some((2, 3)), None]
+    );
+}
+
+#[test]
+fn score_labels_use_font_glyphs() {
+    let labels = [
+        shape::score_label(Score::Cp(80)),
+        shape::score_label(Score::Cp(-130)),
+        shape::score_label(Score::Cp(0)),
+        shape::score_label(Score::Mate(3)),
+        shape::score_label(Score::Mate(-2)),
+    ];
+
+    assert_eq!(labels, ["+0.8", "-1.3", "+0.0", "#3", "-#2"]);
+    for label in &labels {
+        assert!(label.chars().all(|c| font::glyph(c).is_some()));
+    }
+    assert_eq!(font::measure("#3", 10.0).height, 10.0);
+    assert!(font::measure("+0.8", 10.0).width > font::measure("#3", 10.0).width);
+}
+
+#[test]
+fn multipv_lines_become_weighted_arrows() {
+    let line = |multipv, from, to, score| Info {
+        multipv: Some(multipv),
+        score: Some(score),
+        pv: vec![ChessMove::new(from, to, None)],
+        ..Info::default()
+    };
+    let lines = [
+        line(2, Square::D2, Square::D4, Score::Cp(20)),
+        line(1, Square::E2, Square::E4, Score::Cp(40)),
+        Info::default(),
+    ];
+
+    let shapes = shape::multipv(&lines, Brush::Blue);
+
+    assert_eq!(shapes.len(), 2);
+    assert_eq!(
+        shapes[0].mark,
+        Mark::Arrow {
+            from: Square::E2,
+            to: Square::E4
+        }
+    );
+    assert_eq!(shapes[0].label.as_deref(), Some("+0.4"));
+    assert_eq!(shapes[1].label.as_deref(), Some("+0.2"));
+    assert!(shapes[0].weight > shapes[1].weight);
+}
@@ -0,0 +1,187 @@
+mod common;
+
+use std::time::Duration;
+
+use chess::{ChessMove, Square};
+use iced_chess::{
+    chess::GameState,
+    uci::{
+        BestMove, Bound, Engine, EngineConfig, EngineOption, Go, Info, OptionKind, Output, Score,
+    },
+};
+
+use common::mv;
+
+fn scripted() -> EngineConfig {
+    EngineConfig::new("sh")
+        .arg(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/data/scripted_engine.sh"
+        ))
+        .option("Hash", "32")
+}
+
+#[test]
+fn parse_info() {
+    let info = Info::parse(
+        "info depth 12 seldepth 18 multipv 2 score cp -35 upperbound nodes 123456 nps 987654 \
+         hashfull 12 time 250 pv e7e5 g1f3",
+    )
+    .unwrap();
+
+    assert_eq!(info.depth, Some(12));
+    assert_eq!(info.seldepth, Some(18));
+    assert_eq!(info.multipv, Some(2));
+    assert_eq!(info.score, Some(Score::Cp(-35)));
+    assert_eq!(info.bound, Bound::Upper);
+    assert_eq!(info.nodes, Some(123456));
+    assert_eq!(info.nps, Some(987654));
+    assert_eq!(info.time, Some(Duration::from_millis(250)));
+    assert_eq!(
+        info.pv,
+        vec![mv(Square::E7, Square::E5), mv(Square::G1, Square::F3)]
+    );
+
+    let mate = Info::parse("info depth 30 score mate -4 pv a7a8q").unwrap();
+    assert_eq!(mate.score, Some(Score::Mate(-4)));
+    assert_eq!(
+        mate.pv,
+        vec![ChessMove::new(
+            Square::A7,
+            Square::A8,
+            Some(chess::Piece::Queen)
+        )]
+    );
+
+    assert_eq!(Info::parse("bestmove e2e4"), None);
+}
+
+#[test]
+fn parse_bestmove_and_options() {
+    assert_eq!(
+        BestMove::parse("bestmove e2e4 ponder e7e5"),
+        Some(BestMove {
+            mv: Some(mv(Square::E2, Square::E4)),
+            ponder: Some(mv(Square::E7, Square::E5)),
+        })
+    );
+    assert_eq!(
+        BestMove::parse("bestmove (none)"),
+        Some(BestMove {
+            mv: None,
+            ponder: None
+        })
+    );
+
+    let option = EngineOption::parse("option name Skill Level type spin default 20 min 0 max 20");
+    assert_eq!(
+        option,
+        Some(EngineOption {
+            name: "Skill Level".into(),
+            kind: OptionKind::Spin {
+                default: 20,
+                min: 0,
+                max: 20
+            },
+        })
+    );
+    let option = EngineOption::parse("option name Debug Log File type string default <empty>");
+    assert_eq!(
+        option.map(|option| option.kind),
+        Some(OptionKind::String {
+            default: String::new()
+        })
+    );
+}
+
+#[test]
+fn go_command() {
+    assert_eq!(
+        Go::movetime(Duration::from_secs(2)).to_string(),
+        "go movetime 2000"
+    );
+    assert_eq!(Go::depth(8).to_string(), "go depth 8");
+    assert_eq!(Go::nodes(10_000).to_string(), "go nodes 10000");
+    assert_eq!(Go::infinite().to_string(), "go infinite");
+
+    let go = Go {
+        wtime: Some(Duration::from_secs(60)),
+        btime: Some(Duration::from_secs(30)),
+        winc: Some(Duration::from_secs(1)),
+        binc: Some(Duration::from_secs(1)),
+        movestogo: Some(10),
+        ..Go::default()
+    };
+    assert_eq!(
+        go.to_string(),
+        "go wtime 60000 btime 30000 winc 1000 binc 1000 movestogo 10"
+    );
+}
+
+#[tokio::test]
+async fn scripted_engine_search() {
+    let mut engine = Engine::spawn(&scripted()).await.unwrap();
+
+    let info = engine.info();
+    assert_eq!(info.name, "Scripted Engine");
+    assert_eq!(info.author, "iced-chess");
+    assert_eq!(info.options.len(), 4);
+    assert_eq!(info.options[3].kind, OptionKind::Button);
+
+    engine.new_game().await.unwrap();
+    engine
+        .position(&GameState::default(), &[mv(Square::D2, Square::D4)])
+        .await
+        .unwrap();
+    engine.go(&Go::depth(2)).await.unwrap();
+
+    let Output::Info(echo) = engine.next_output().await.unwrap() else {
+        panic!("expected info");
+    };
+    assert_eq!(echo.string.as_deref(), Some("go depth 2"));
+
+    let Output::Info(first) = engine.next_output().await.unwrap() else {
+        panic!("expected info");
+    };
+    assert_eq!(first.depth, Some(1));
+    assert_eq!(first.score, Some(Score::Cp(25)));
+
+    let Output::Info(second) = engine.next_output().await.unwrap() else {
+        panic!("expected info");
+    };
+    assert_eq!(second.score, Some(Score::Mate(3)));
+    assert_eq!(second.bound, Bound::Lower);
+    assert_eq!(second.pv.len(), 2);
+
+    assert_eq!(
+        engine.next_output().await.unwrap(),
+        Output::BestMove(BestMove {
+            mv: Some(mv(Square::E2, Square::E4)),
+            ponder: Some(mv(Square::E7, Square::E5)),
+        })
+    );
+
+    engine.quit().await.unwrap();
+}
+
+#[tokio::test]
+async fn scripted_engine_stop() {
+    let mut engine = Engine::spawn(&scripted()).await.unwrap();
+
+    engine.go(&Go::infinite()).await.unwrap();
+    assert!(matches!(
+        engine.next_output().await.unwrap(),
+        Output::Info(_)
+    ));
+
+    engine.stop().await.unwrap();
+    assert_eq!(
+        engine.next_output().await.unwrap(),
+        Output::BestMove(BestMove {
+            mv: Some(mv(Square::D2, Square::D4)),
+            ponder: None,
+        })
+    );
+
+    engine.quit().await.unwrap();
+}